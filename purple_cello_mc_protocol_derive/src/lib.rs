@@ -0,0 +1,487 @@
+// Yeahbut October 2026
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident,
+    LitInt, PathArguments, Type,
+};
+
+#[proc_macro_derive(Packet, attributes(packet))]
+pub fn derive_packet(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, true)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(PacketArray, attributes(packet))]
+pub fn derive_packet_array(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    var_int: bool,
+    var_long: bool,
+    remaining: bool,
+    json: bool,
+    max_length: Option<LitInt>,
+    since: Option<Ident>,
+    until: Option<Ident>,
+}
+
+enum Codec {
+    Primitive(Ident),
+    VarInt,
+    VarIntArray,
+    VarLong,
+    Uuid,
+    String,
+    ByteArray,
+    FixedBytes,
+    Remaining,
+    Component,
+    JsonComponent,
+    Array(Type),
+    Optional(Box<Codec>),
+    Nested(Type),
+}
+
+fn expand(input: DeriveInput, is_packet: bool) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut packet_id: Option<LitInt> = None;
+    let mut since: Option<Ident> = None;
+    let mut until: Option<Ident> = None;
+    let mut version_ids: Vec<(Ident, LitInt)> = vec![];
+    for attr in &input.attrs {
+        if !attr.path().is_ident("packet") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                packet_id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("since") {
+                since = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("until") {
+                until = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("ids") {
+                meta.parse_nested_meta(|meta| {
+                    let version = match meta.path.get_ident() {
+                        Some(version) => version.clone(),
+                        None => return Err(meta.error("expected a version")),
+                    };
+                    version_ids.push((version, meta.value()?.parse()?));
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("unsupported packet attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new(
+            name.span(), "packets can only be derived for structs")),
+    };
+    let named = match fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unit => vec![],
+        Fields::Unnamed(_) => return Err(syn::Error::new(
+            name.span(), "packets must use named fields")),
+    };
+
+    let mut getters: Vec<TokenStream2> = vec![];
+    let mut converters: Vec<TokenStream2> = vec![];
+    let mut idents: Vec<&Ident> = vec![];
+    let version_type = quote!(
+        ::purple_cello_mc_protocol::version::ProtocolVersion);
+    for field in named {
+        let ident = field.ident.as_ref().unwrap();
+        let options = field_options(field)?;
+        if !is_packet && (options.since.is_some() || options.until.is_some()) {
+            return Err(syn::Error::new(field.span(),
+                "version specific fields are only supported on packets"));
+        }
+        let codec = codec(&field.ty, &options)?;
+        let field_name = ident.to_string();
+        let get = get_expr(&codec, &options, &field_name);
+        let convert = convert_expr(&codec, quote!(&self.#ident));
+        let mut conditions: Vec<TokenStream2> = vec![];
+        if let Some(since) = &options.since {
+            conditions.push(quote!(version >= #version_type::#since));
+        }
+        if let Some(until) = &options.until {
+            conditions.push(quote!(version <= #version_type::#until));
+        }
+        if conditions.is_empty() {
+            getters.push(quote_spanned! {field.span()=>
+                let #ident = #get;
+            });
+            converters.push(convert);
+        } else {
+            getters.push(quote_spanned! {field.span()=>
+                let #ident = if #(#conditions)&&* {
+                    #get
+                } else {
+                    Default::default()
+                };
+            });
+            converters.push(quote! {
+                if #(#conditions)&&* {
+                    #convert
+                }
+            });
+        }
+        idents.push(ident);
+    }
+
+    let construct = match fields {
+        Fields::Unit => quote!(Self),
+        _ => quote!(Self { #(#idents),* }),
+    };
+    let mc_types = quote!(::purple_cello_mc_protocol::mc_types);
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    if is_packet {
+        let packet_id = match packet_id {
+            Some(packet_id) => packet_id,
+            None => return Err(syn::Error::new(
+                name.span(), "missing #[packet(id = ...)] attribute")),
+        };
+        let optional_version = |version: &Option<Ident>| match version {
+            Some(version) => quote!(Some(#version_type::#version)),
+            None => quote!(None),
+        };
+        let since = optional_version(&since);
+        let until = optional_version(&until);
+        let (versions, ids): (Vec<_>, Vec<_>) =
+            version_ids.iter().cloned().unzip();
+        Ok(quote! {
+            impl #impl_generics #mc_types::Packet for #name #ty_generics
+            #where_clause
+            {
+                const PACKET_ID: i32 = #packet_id;
+
+                fn get(data: &mut &[u8]) -> #mc_types::Result<Self> {
+                    <Self as #mc_types::Packet>::get_versioned(
+                        data, #version_type::LATEST)
+                }
+
+                fn convert(&self) -> #mc_types::Result<Vec<u8>> {
+                    let version = #version_type::LATEST;
+                    let _ = version;
+                    let mut data: Vec<u8> = vec![];
+                    data.append(&mut #mc_types::convert_var_int(
+                        <Self as #mc_types::Packet>::PACKET_ID));
+                    #(#converters)*
+
+                    Ok(data)
+                }
+
+                const VERSIONED_IDS: [Option<i32>;
+                    ::purple_cello_mc_protocol::version::VERSION_COUNT] =
+                    #mc_types::versioned_ids(#packet_id, #since, #until, &[
+                        #((#version_type::#versions, #ids)),*
+                    ]);
+
+                fn get_versioned(
+                    data: &mut &[u8],
+                    version: #version_type,
+                ) -> #mc_types::Result<Self> {
+                    use #mc_types::ResultContext;
+                    let _ = version;
+                    #(#getters)*
+                    Ok(#construct)
+                }
+
+                fn convert_versioned(
+                    &self,
+                    version: #version_type,
+                ) -> #mc_types::Result<Vec<u8>> {
+                    let packet_id =
+                        <Self as #mc_types::Packet>::packet_id_for(version)
+                            .ok_or(#mc_types::PacketError
+                                ::UnavailableInVersion(version))?;
+                    let mut data: Vec<u8> = vec![];
+                    data.append(&mut #mc_types::convert_var_int(packet_id));
+                    #(#converters)*
+
+                    Ok(data)
+                }
+            }
+        })
+    } else {
+        if packet_id.is_some() || since.is_some() || until.is_some() ||
+            !version_ids.is_empty()
+        {
+            return Err(syn::Error::new(name.span(),
+                "packet arrays do not have packet ids or versions"));
+        }
+        Ok(quote! {
+            impl #impl_generics #mc_types::PacketArray for #name #ty_generics
+            #where_clause
+            {
+                fn get(data: &mut &[u8]) -> #mc_types::Result<Self> {
+                    use #mc_types::ResultContext;
+                    let version = #version_type::LATEST;
+                    let _ = version;
+                    #(#getters)*
+                    Ok(#construct)
+                }
+
+                fn try_convert(&self) -> #mc_types::Result<Vec<u8>> {
+                    let version = #version_type::LATEST;
+                    let _ = version;
+                    let mut data: Vec<u8> = vec![];
+                    #(#converters)*
+
+                    Ok(data)
+                }
+            }
+        })
+    }
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in &field.attrs {
+        if !attr.path().is_ident("packet") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("var_int") {
+                options.var_int = true;
+            } else if meta.path.is_ident("var_long") {
+                options.var_long = true;
+            } else if meta.path.is_ident("remaining") {
+                options.remaining = true;
+            } else if meta.path.is_ident("json") {
+                options.json = true;
+            } else if meta.path.is_ident("max_length") {
+                options.max_length = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("since") {
+                options.since = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("until") {
+                options.until = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported field attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn type_name(ty: &Type) -> Option<(String, Option<&Type>)> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    let argument = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => {
+            match arguments.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        },
+        _ => None,
+    };
+    Some((segment.ident.to_string(), argument))
+}
+
+fn codec(ty: &Type, options: &FieldOptions) -> syn::Result<Codec> {
+    if let Type::Array(array) = ty {
+        return match type_name(&array.elem) {
+            Some((name, _)) if name == "u8" => Ok(Codec::FixedBytes),
+            _ => Err(syn::Error::new(
+                ty.span(), "fixed arrays are only supported for u8")),
+        };
+    }
+    let (name, argument) = match type_name(ty) {
+        Some(name) => name,
+        None => return Ok(Codec::Nested(ty.clone())),
+    };
+    let integer_only = |codec: Codec, expected: &str| {
+        if name == expected {
+            Ok(codec)
+        } else {
+            Err(syn::Error::new(
+                ty.span(), format!("this encoding requires {}", expected)))
+        }
+    };
+    if options.var_int {
+        return match name.as_str() {
+            "Option" => Ok(Codec::Optional(Box::new(
+                codec(argument.unwrap(), options)?))),
+            "Vec" => match argument.and_then(type_name) {
+                Some((inner_name, _)) if inner_name == "i32" =>
+                    Ok(Codec::VarIntArray),
+                _ => Err(syn::Error::new(
+                    ty.span(), "this encoding requires Vec<i32>")),
+            },
+            _ => integer_only(Codec::VarInt, "i32"),
+        };
+    }
+    if options.var_long {
+        return match name.as_str() {
+            "Option" => Ok(Codec::Optional(Box::new(
+                codec(argument.unwrap(), options)?))),
+            _ => integer_only(Codec::VarLong, "i64"),
+        };
+    }
+    Ok(match name.as_str() {
+        "bool" | "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" |
+        "i64" | "f32" | "f64" => Codec::Primitive(
+            Ident::new(&name, ty.span())),
+        "u128" => Codec::Uuid,
+        "String" => Codec::String,
+        "TextComponent" | "Chat" if options.json => Codec::JsonComponent,
+        "TextComponent" | "Chat" => Codec::Component,
+        "Option" => match argument {
+            Some(inner) => Codec::Optional(Box::new(codec(inner, options)?)),
+            None => return Err(syn::Error::new(
+                ty.span(), "Option requires a type argument")),
+        },
+        "Vec" => match argument {
+            Some(inner) => match type_name(inner) {
+                Some((inner_name, _)) if inner_name == "u8" => {
+                    if options.remaining {
+                        Codec::Remaining
+                    } else {
+                        Codec::ByteArray
+                    }
+                },
+                _ => Codec::Array(inner.clone()),
+            },
+            None => return Err(syn::Error::new(
+                ty.span(), "Vec requires a type argument")),
+        },
+        _ => Codec::Nested(ty.clone()),
+    })
+}
+
+fn get_expr(
+    codec: &Codec,
+    options: &FieldOptions,
+    field_name: &str,
+) -> TokenStream2 {
+    let mc_types = quote!(::purple_cello_mc_protocol::mc_types);
+    let max_length = options.max_length.as_ref();
+    match codec {
+        Codec::Primitive(ty) => {
+            let getter = Ident::new(&format!("get_{}", ty), ty.span());
+            quote!(#mc_types::#getter(data).field(#field_name)?)
+        },
+        Codec::VarInt =>
+            quote!(#mc_types::get_var_int(data).field(#field_name)?),
+        Codec::VarIntArray => match max_length {
+            Some(max) => quote!(
+                #mc_types::get_var_int_array_limited(data, #max)
+                    .field(#field_name)?),
+            None => quote!(#mc_types::get_var_int_array(data)
+                .field(#field_name)?),
+        },
+        Codec::VarLong =>
+            quote!(#mc_types::get_var_long(data).field(#field_name)?),
+        Codec::Uuid =>
+            quote!(#mc_types::get_uuid(data).field(#field_name)?),
+        Codec::String => match max_length {
+            Some(max) => quote!(#mc_types::get_string_limited(data, #max)
+                .field(#field_name)?),
+            None => quote!(#mc_types::get_string(data).field(#field_name)?),
+        },
+        Codec::ByteArray => match max_length {
+            Some(max) => quote!(#mc_types::get_byte_array_limited(data, #max)
+                .field(#field_name)?),
+            None =>
+                quote!(#mc_types::get_byte_array(data).field(#field_name)?),
+        },
+        Codec::FixedBytes => quote!(
+            #mc_types::get_byte_array_fixed(data).field(#field_name)?),
+        Codec::Remaining =>
+            quote!(#mc_types::get_remaining(data).to_vec()),
+        Codec::Component => quote!(
+            #mc_types::get_component(data, version).field(#field_name)?),
+        Codec::JsonComponent => match max_length {
+            Some(max) => quote!(
+                #mc_types::get_json_component_limited(data, #max)
+                    .field(#field_name)?),
+            None => quote!(#mc_types::get_json_component(data)
+                .field(#field_name)?),
+        },
+        Codec::Array(ty) => match max_length {
+            Some(max) => quote!(
+                <#ty as #mc_types::PacketArray>::get_array_limited(data, #max)
+                    .field(#field_name)?),
+            None => quote!(<#ty as #mc_types::PacketArray>::get_array(data)
+                .field(#field_name)?),
+        },
+        Codec::Optional(inner) => {
+            let inner = get_expr(inner, options, field_name);
+            quote! {
+                if #mc_types::get_bool(data).field(#field_name)? {
+                    Some(#inner)
+                } else {
+                    None
+                }
+            }
+        },
+        Codec::Nested(ty) => quote!(
+            <#ty as #mc_types::PacketArray>::get(data).field(#field_name)?),
+    }
+}
+
+fn convert_expr(codec: &Codec, value: TokenStream2) -> TokenStream2 {
+    let mc_types = quote!(::purple_cello_mc_protocol::mc_types);
+    match codec {
+        Codec::Primitive(ty) => {
+            let converter = Ident::new(&format!("convert_{}", ty), ty.span());
+            quote!(data.append(&mut #mc_types::#converter(*#value));)
+        },
+        Codec::VarInt =>
+            quote!(data.append(&mut #mc_types::convert_var_int(*#value));),
+        Codec::VarIntArray => quote!(
+            data.append(&mut #mc_types::convert_var_int_array(#value));),
+        Codec::VarLong =>
+            quote!(data.append(&mut #mc_types::convert_var_long(*#value));),
+        Codec::Uuid =>
+            quote!(data.append(&mut #mc_types::convert_uuid(*#value));),
+        Codec::String =>
+            quote!(data.append(&mut #mc_types::convert_string(#value));),
+        Codec::ByteArray =>
+            quote!(data.append(&mut #mc_types::convert_byte_array(#value));),
+        Codec::FixedBytes | Codec::Remaining =>
+            quote!(data.extend_from_slice(#value);),
+        Codec::Component => quote!(data.append(
+            &mut #mc_types::convert_component(#value, version)?);),
+        Codec::JsonComponent => quote!(data.append(
+            &mut #mc_types::convert_json_component(#value)?);),
+        Codec::Array(ty) => quote!(data.append(
+            &mut <#ty as #mc_types::PacketArray>::try_convert_array(
+                #value)?);),
+        Codec::Optional(inner) => {
+            let inner = convert_expr(inner, quote!(value));
+            quote! {
+                match #value {
+                    Some(value) => {
+                        data.append(&mut #mc_types::convert_bool(true));
+                        #inner
+                    },
+                    None => data.append(&mut #mc_types::convert_bool(false)),
+                }
+            }
+        },
+        Codec::Nested(ty) => quote!(data.append(
+            &mut <#ty as #mc_types::PacketArray>::try_convert(#value)?);),
+    }
+}
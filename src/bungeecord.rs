@@ -0,0 +1,82 @@
+// Yeahbut October 2026
+
+use std::fmt;
+use std::net::IpAddr;
+
+use crate::mc_types::{self, PacketError, Result};
+use crate::handshake::serverbound::Handshake;
+use crate::login::clientbound::LoginSuccessProperty;
+
+/// Player info a BungeeCord proxy packs into the handshake server address
+/// as `host\0clientIp\0uuid\0propertiesJson`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BungeeCordForwarding {
+    pub hostname: String,
+    pub client_ip: IpAddr,
+    pub uuid: u128,
+    pub properties: Vec<LoginSuccessProperty>,
+}
+
+impl BungeeCordForwarding {
+    pub fn new(hostname: impl Into<String>, client_ip: IpAddr, uuid: u128)
+        -> Self
+    {
+        Self {
+            hostname: hostname.into(),
+            client_ip,
+            uuid,
+            properties: vec![],
+        }
+    }
+
+    pub fn with_properties(
+        mut self,
+        properties: Vec<LoginSuccessProperty>,
+    ) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    pub fn parse(server_address: &str) -> Result<Self> {
+        let mut parts = server_address.splitn(4, '\0');
+        let hostname = parts.next().unwrap_or_default();
+        let (client_ip, uuid) = match (parts.next(), parts.next()) {
+            (Some(client_ip), Some(uuid)) => (client_ip, uuid),
+            _ => return Err(PacketError::InvalidForwarding(
+                "missing client address or UUID").into()),
+        };
+        let properties = match parts.next() {
+            Some(properties) => serde_json::from_str(properties)?,
+            None => vec![],
+        };
+        Ok(Self {
+            hostname: hostname.to_string(),
+            client_ip: client_ip.parse().map_err(|_|
+                PacketError::InvalidForwarding("invalid client address"))?,
+            uuid: mc_types::uuid_string_to_u128(uuid)?,
+            properties,
+        })
+    }
+
+    pub fn from_handshake(handshake: &Handshake) -> Result<Self> {
+        Self::parse(&handshake.server_address)
+    }
+
+    /// Replaces the handshake's server address with the forwarded one.
+    pub fn apply(&self, handshake: &mut Handshake) {
+        handshake.server_address = self.to_string();
+    }
+}
+
+impl fmt::Display for BungeeCordForwarding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\0{}\0{:032x}",
+            self.hostname, self.client_ip, self.uuid)?;
+        if !self.properties.is_empty() {
+            let properties = serde_json::to_string(&self.properties)
+                .map_err(|_| fmt::Error)?;
+            write!(f, "\0{}", properties)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+// Yeahbut October 2026
+
+pub mod clientbound {
+
+    use crate::mc_types::{Packet, PacketArray};
+    use crate::text::TextComponent;
+
+    crate::packet_enum! {
+        #[allow(clippy::large_enum_variant)]
+        pub enum Configuration (Configuration, Clientbound) {
+            CookieRequest(CookieRequest),
+            PluginMessage(PluginMessage),
+            Disconnect(Disconnect),
+            FinishConfiguration(FinishConfiguration),
+            KeepAlive(KeepAlive),
+            Ping(Ping),
+            ResetChat(ResetChat),
+            RegistryData(RegistryData),
+            ResourcePackPop(ResourcePackPop),
+            ResourcePackPush(ResourcePackPush),
+            StoreCookie(StoreCookie),
+            Transfer(Transfer),
+            FeatureFlags(FeatureFlags),
+            UpdateTags(UpdateTags),
+            KnownPacks(KnownPacks),
+            CustomReportDetails(CustomReportDetails),
+            ServerLinks(ServerLinks),
+        }
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x00, since = V1_20_5)]
+    pub struct CookieRequest {
+        pub key: String,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x01, since = V1_20_2,
+        ids(V1_20_2 = 0x00, V1_20_3 = 0x00))]
+    pub struct PluginMessage {
+        pub channel: String,
+        #[packet(remaining)]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x02, since = V1_20_2,
+        ids(V1_20_2 = 0x01, V1_20_3 = 0x01))]
+    pub struct Disconnect {
+        pub reason: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x03, since = V1_20_2,
+        ids(V1_20_2 = 0x02, V1_20_3 = 0x02))]
+    pub struct FinishConfiguration {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x04, since = V1_20_2,
+        ids(V1_20_2 = 0x03, V1_20_3 = 0x03))]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x05, since = V1_20_2,
+        ids(V1_20_2 = 0x04, V1_20_3 = 0x04))]
+    pub struct Ping {
+        pub id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x06, since = V1_20_5)]
+    pub struct ResetChat {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x07, since = V1_20_2,
+        ids(V1_20_2 = 0x05, V1_20_3 = 0x05))]
+    pub struct RegistryData {
+        #[packet(since = V1_20_5)]
+        pub registry_id: String,
+        #[packet(remaining)]
+        pub entries: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x08, since = V1_20_3, ids(V1_20_3 = 0x06))]
+    pub struct ResourcePackPop {
+        pub uuid: Option<u128>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x09, since = V1_20_2,
+        ids(V1_20_2 = 0x06, V1_20_3 = 0x07))]
+    pub struct ResourcePackPush {
+        #[packet(since = V1_20_3)]
+        pub uuid: u128,
+        pub url: String,
+        #[packet(max_length = 40)]
+        pub hash: String,
+        pub forced: bool,
+        pub prompt: Option<TextComponent>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0A, since = V1_20_5)]
+    pub struct StoreCookie {
+        pub key: String,
+        #[packet(max_length = 5120)]
+        pub payload: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0B, since = V1_20_5)]
+    pub struct Transfer {
+        pub host: String,
+        #[packet(var_int)]
+        pub port: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0C, since = V1_20_2,
+        ids(V1_20_2 = 0x07, V1_20_3 = 0x08))]
+    pub struct FeatureFlags {
+        pub feature_flags: Vec<String>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0D, since = V1_20_2,
+        ids(V1_20_2 = 0x08, V1_20_3 = 0x09))]
+    pub struct UpdateTags {
+        pub registries: Vec<RegistryTags>,
+    }
+
+    #[derive(PacketArray)]
+    pub struct RegistryTags {
+        pub registry: String,
+        pub tags: Vec<Tag>,
+    }
+
+    #[derive(PacketArray)]
+    pub struct Tag {
+        pub name: String,
+        #[packet(var_int)]
+        pub entries: Vec<i32>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0E, since = V1_20_5)]
+    pub struct KnownPacks {
+        pub known_packs: Vec<KnownPack>,
+    }
+
+    #[derive(Clone, PacketArray)]
+    pub struct KnownPack {
+        pub namespace: String,
+        pub id: String,
+        pub version: String,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0F, since = V1_21)]
+    pub struct CustomReportDetails {
+        #[packet(max_length = 32)]
+        pub details: Vec<ReportDetail>,
+    }
+
+    #[derive(PacketArray)]
+    pub struct ReportDetail {
+        #[packet(max_length = 128)]
+        pub title: String,
+        #[packet(max_length = 4096)]
+        pub description: String,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x10, since = V1_21)]
+    pub struct ServerLinks {
+        #[packet(remaining)]
+        pub links: Vec<u8>,
+    }
+
+}
+
+pub mod serverbound {
+
+    use crate::mc_types::Packet;
+
+    pub use super::clientbound::KnownPack;
+
+    crate::packet_enum! {
+        pub enum Configuration (Configuration, Serverbound) {
+            ClientInformation(ClientInformation),
+            CookieResponse(CookieResponse),
+            PluginMessage(PluginMessage),
+            AcknowledgeFinishConfiguration(AcknowledgeFinishConfiguration),
+            KeepAlive(KeepAlive),
+            Pong(Pong),
+            ResourcePackResponse(ResourcePackResponse),
+            KnownPacks(KnownPacks),
+        }
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x00, since = V1_20_2)]
+    pub struct ClientInformation {
+        #[packet(max_length = 16)]
+        pub locale: String,
+        pub view_distance: i8,
+        #[packet(var_int)]
+        pub chat_mode: i32,
+        pub chat_colors: bool,
+        pub displayed_skin_parts: u8,
+        #[packet(var_int)]
+        pub main_hand: i32,
+        pub enable_text_filtering: bool,
+        pub allow_server_listings: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x01, since = V1_20_5)]
+    pub struct CookieResponse {
+        pub key: String,
+        #[packet(max_length = 5120)]
+        pub payload: Option<Vec<u8>>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x02, since = V1_20_2,
+        ids(V1_20_2 = 0x01, V1_20_3 = 0x01))]
+    pub struct PluginMessage {
+        pub channel: String,
+        #[packet(remaining)]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x03, since = V1_20_2,
+        ids(V1_20_2 = 0x02, V1_20_3 = 0x02))]
+    pub struct AcknowledgeFinishConfiguration {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x04, since = V1_20_2,
+        ids(V1_20_2 = 0x03, V1_20_3 = 0x03))]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x05, since = V1_20_2,
+        ids(V1_20_2 = 0x04, V1_20_3 = 0x04))]
+    pub struct Pong {
+        pub id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x06, since = V1_20_2,
+        ids(V1_20_2 = 0x05, V1_20_3 = 0x05))]
+    pub struct ResourcePackResponse {
+        #[packet(since = V1_20_3)]
+        pub uuid: u128,
+        #[packet(var_int)]
+        pub result: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x07, since = V1_20_5)]
+    pub struct KnownPacks {
+        pub known_packs: Vec<KnownPack>,
+    }
+
+}
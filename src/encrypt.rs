@@ -18,7 +18,7 @@ impl McCipher {
         let mut rng = rand::thread_rng();
         let aes_key: [u8; 16] = rng.gen();
         Self {
-            key: aes_key.clone(),
+            key: aes_key,
             state_en: u128::from_be_bytes(aes_key),
             state_de: u128::from_be_bytes(aes_key),
         }
@@ -38,7 +38,7 @@ impl McCipher {
         let aes_key: [u8; 16] = decrypt_rsa(private_key, data)?
             .as_slice()[0..16].try_into().unwrap();
         Ok(Self {
-            key: aes_key.clone(),
+            key: aes_key,
             state_en: u128::from_be_bytes(aes_key),
             state_de: u128::from_be_bytes(aes_key),
         })
@@ -76,8 +76,7 @@ impl McCipher {
             &self.state_de.to_be_bytes());
         cipher.decrypt_block(&mut block);
         self.state_de = (self.state_de << 8) + (data as u128);
-        let data = data ^ block[0];
-        data
+        data ^ block[0]
     }
 }
 
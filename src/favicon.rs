@@ -0,0 +1,133 @@
+// Yeahbut October 2026
+
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+use crate::mc_types::{PacketError, Result};
+
+pub const FAVICON_PREFIX: &str = "data:image/png;base64,";
+pub const FAVICON_SIZE: u32 = 64;
+
+const PNG_SIGNATURE: [u8; 8] =
+    [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Builds a favicon data URI, rescaling the image to 64x64 if `rescale` is
+/// set and rejecting any other size otherwise.
+pub fn from_png(png: &[u8], rescale: bool) -> Result<String> {
+    let (width, height) = png_dimensions(png)?;
+    if width == FAVICON_SIZE && height == FAVICON_SIZE {
+        return Ok(encode(png));
+    }
+    if !rescale {
+        return Err(PacketError::InvalidFavicon("image is not 64x64").into());
+    }
+    Ok(encode(&rescale_png(png)?))
+}
+
+pub fn from_file(path: impl AsRef<Path>, rescale: bool) -> Result<String> {
+    from_png(&std::fs::read(path)?, rescale)
+}
+
+pub fn to_png(favicon: &str) -> Result<Vec<u8>> {
+    let data = favicon.strip_prefix(FAVICON_PREFIX).ok_or(
+        PacketError::InvalidFavicon("missing PNG data URI prefix"))?;
+    // Older servers wrap the base64 data in lines
+    let data: String = data.chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let png = STANDARD.decode(data)
+        .map_err(|_| PacketError::InvalidFavicon("invalid base64"))?;
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err(
+            PacketError::InvalidFavicon("invalid PNG signature").into());
+    }
+    Ok(png)
+}
+
+pub fn png_dimensions(png: &[u8]) -> Result<(u32, u32)> {
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err(
+            PacketError::InvalidFavicon("invalid PNG signature").into());
+    }
+    let header = png.get(8..24)
+        .filter(|header| &header[4..8] == b"IHDR")
+        .ok_or(PacketError::InvalidFavicon("missing IHDR chunk"))?;
+    Ok((
+        u32::from_be_bytes(header[8..12].try_into().unwrap()),
+        u32::from_be_bytes(header[12..16].try_into().unwrap()),
+    ))
+}
+
+pub fn rescale_png(png: &[u8]) -> Result<Vec<u8>> {
+    let invalid = |_| PacketError::InvalidFavicon("could not decode PNG");
+    let mut decoder = Decoder::new(png);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(invalid)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(invalid)?;
+    let pixels = to_rgba(&buffer[..info.buffer_size()], info.color_type);
+    let (width, height) = (info.width as usize, info.height as usize);
+    if width == 0 || height == 0 {
+        return Err(PacketError::InvalidFavicon("image is empty").into());
+    }
+
+    // Average the source pixels covering each target pixel
+    let size = FAVICON_SIZE as usize;
+    let mut scaled = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        let (y0, y1) = source_range(y, size, height);
+        for x in 0..size {
+            let (x0, x1) = source_range(x, size, width);
+            let mut sum = [0u64; 4];
+            for row in y0..y1 {
+                for column in x0..x1 {
+                    let pixel = (row * width + column) * 4;
+                    for (channel, total) in sum.iter_mut().enumerate() {
+                        *total += pixels[pixel + channel] as u64;
+                    }
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as u64;
+            scaled.extend(sum.iter().map(|total| (total / count) as u8));
+        }
+    }
+
+    let mut out = vec![];
+    let mut encoder = Encoder::new(&mut out, FAVICON_SIZE, FAVICON_SIZE);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let encode_error =
+        |_| PacketError::InvalidFavicon("could not encode PNG");
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    writer.write_image_data(&scaled).map_err(encode_error)?;
+    writer.finish().map_err(encode_error)?;
+    Ok(out)
+}
+
+fn encode(png: &[u8]) -> String {
+    format!("{}{}", FAVICON_PREFIX, STANDARD.encode(png))
+}
+
+fn to_rgba(buffer: &[u8], color_type: ColorType) -> Vec<u8> {
+    match color_type {
+        ColorType::Rgba => buffer.to_vec(),
+        ColorType::Rgb => buffer.chunks(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 0xFF])
+            .collect(),
+        ColorType::GrayscaleAlpha => buffer.chunks(2)
+            .flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+            .collect(),
+        _ => buffer.iter()
+            .flat_map(|gray| [*gray, *gray, *gray, 0xFF])
+            .collect(),
+    }
+}
+
+fn source_range(target: usize, size: usize, source: usize) -> (usize, usize) {
+    let start = target * source / size;
+    let end = ((target + 1) * source / size).max(start + 1);
+    (start, end)
+}
@@ -2,56 +2,147 @@
 
 pub mod serverbound {
 
-    use crate::mc_types::{self, Result, Packet, PacketError};
+    use std::fmt;
 
-    pub enum HandshakeEnum {
-        Handshake(Handshake),
-    }
+    use crate::mc_types::{Packet, PacketError, Result};
+    use crate::version::ProtocolVersion;
+    use crate::legacy_ping::LegacyPing;
 
-    impl HandshakeEnum {
-        pub async fn read<T: mc_types::ProtocolRead>(
-            conn: &mut T,
-        ) -> Result<Self> {
-            let mut data = conn.read_data().await?;
-            let packet_id = mc_types::get_var_int(&mut data)?;
-            if packet_id == Handshake::packet_id() {
-                return Ok(Self::Handshake(Handshake::get(&mut data)?))
-            } else {
-                return Err(Box::new(PacketError::InvalidPacketId))
-            }
+    crate::packet_enum! {
+        pub enum HandshakeEnum (Handshake, Serverbound) {
+            Handshake(Handshake),
         }
     }
 
+    pub enum HandshakeStart {
+        Handshake(Handshake),
+        LegacyPing(LegacyPing),
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0)]
     pub struct Handshake {
+        #[packet(var_int)]
         pub protocol_version: i32,
+        // Vanilla allows 255 characters but BungeeCord forwarding with
+        // skin properties needs a couple of thousand
+        #[packet(max_length = 8192)]
         pub server_address: String,
         pub server_port: u16,
+        #[packet(var_int)]
         pub next_state: i32,
     }
 
-    impl Packet for Handshake {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HandshakeIntent {
+        Status = 1,
+        Login = 2,
+        Transfer = 3,
+    }
 
-        fn packet_id() -> i32 {0}
+    impl HandshakeIntent {
+        pub fn from_next_state(next_state: i32) -> Result<Self> {
+            match next_state {
+                1 => Ok(Self::Status),
+                2 => Ok(Self::Login),
+                3 => Ok(Self::Transfer),
+                _ => Err(PacketError::InvalidNextState(next_state).into()),
+            }
+        }
 
-        fn get(data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                protocol_version: mc_types::get_var_int(data)?,
-                server_address: mc_types::get_string(data)?,
-                server_port: mc_types::get_u16(data),
-                next_state: mc_types::get_var_int(data)?,
-            })
+        pub const fn next_state(self) -> i32 {
+            self as i32
         }
+    }
 
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_var_int(self.protocol_version));
-            data.append(&mut mc_types::convert_string(&self.server_address));
-            data.append(&mut mc_types::convert_u16(self.server_port));
-            data.append(&mut mc_types::convert_var_int(self.next_state));
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ForgeMarker {
+        Fml,
+        Fml2,
+        Fml3,
+    }
 
-            data
+    impl ForgeMarker {
+        pub const fn marker(self) -> &'static str {
+            match self {
+                Self::Fml => "\0FML\0",
+                Self::Fml2 => "\0FML2\0",
+                Self::Fml3 => "\0FML3\0",
+            }
         }
+    }
+
+    /// The server address split into the hostname the client connected to
+    /// and the data clients and proxies append after it.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ServerAddress {
+        pub hostname: String,
+        // Left by clients that resolved the address through an SRV record
+        pub trailing_dot: bool,
+        pub forge: Option<ForgeMarker>,
+        pub suffix: String,
+    }
 
+    impl ServerAddress {
+        pub fn parse(address: &str) -> Self {
+            let (hostname, mut suffix) = match address.find('\0') {
+                Some(index) => address.split_at(index),
+                None => (address, ""),
+            };
+            let forge = [
+                ForgeMarker::Fml,
+                ForgeMarker::Fml2,
+                ForgeMarker::Fml3,
+            ].into_iter()
+                .find(|forge| suffix.starts_with(forge.marker()));
+            if let Some(forge) = forge {
+                suffix = &suffix[forge.marker().len()..];
+            }
+            let trailing_dot = hostname.ends_with('.');
+            Self {
+                hostname: hostname
+                    .strip_suffix('.')
+                    .unwrap_or(hostname)
+                    .to_string(),
+                trailing_dot,
+                forge,
+                suffix: suffix.to_string(),
+            }
+        }
+    }
+
+    impl fmt::Display for ServerAddress {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.hostname)?;
+            if self.trailing_dot {
+                f.write_str(".")?;
+            }
+            if let Some(forge) = self.forge {
+                f.write_str(forge.marker())?;
+            }
+            f.write_str(&self.suffix)
+        }
+    }
+
+    impl Handshake {
+        pub fn version(&self) -> Result<ProtocolVersion> {
+            ProtocolVersion::from_protocol(self.protocol_version)
+        }
+
+        /// Transfers are only accepted from 1.20.5 clients onwards.
+        pub fn intent(&self) -> Result<HandshakeIntent> {
+            let intent = HandshakeIntent::from_next_state(self.next_state)?;
+            if intent == HandshakeIntent::Transfer &&
+                self.protocol_version < ProtocolVersion::V1_20_5.protocol()
+            {
+                return Err(PacketError::InvalidNextState(self.next_state)
+                    .into());
+            }
+            Ok(intent)
+        }
+
+        pub fn address(&self) -> ServerAddress {
+            ServerAddress::parse(&self.server_address)
+        }
     }
 }
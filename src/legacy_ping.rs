@@ -0,0 +1,276 @@
+// Yeahbut October 2026
+
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::timeout;
+
+use crate::mc_types::{self, PacketError, Result};
+use crate::status::clientbound::StatusResponseData;
+use crate::text::{self, TextComponent};
+
+pub const LEGACY_PING: u8 = 0xFE;
+pub const LEGACY_PING_PAYLOAD: u8 = 0x01;
+pub const LEGACY_PLUGIN_MESSAGE: u8 = 0xFA;
+pub const LEGACY_KICK: u8 = 0xFF;
+pub const PING_HOST_CHANNEL: &str = "MC|PingHost";
+
+// Vanilla servers report this so legacy clients show "incompatible"
+pub const LEGACY_PROTOCOL_VERSION: i32 = 127;
+
+// Clients before 1.6 send nothing after the ping and wait for a reply
+const PAYLOAD_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegacyPing {
+    /// Beta 1.8 to 1.3, a lone 0xFE
+    Beta,
+    /// 1.4 and 1.5, 0xFE 0x01
+    V1_4,
+    /// 1.6, 0xFE 0x01 followed by an MC|PingHost plugin message
+    V1_6(LegacyPingHost),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyPingHost {
+    pub protocol_version: u8,
+    pub hostname: String,
+    pub port: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyPingResponse {
+    pub protocol_version: i32,
+    pub version_name: String,
+    pub motd: String,
+    pub online_players: i32,
+    pub max_players: i32,
+}
+
+pub fn is_legacy_ping(first_byte: u8) -> bool {
+    first_byte == LEGACY_PING
+}
+
+impl LegacyPing {
+    pub fn get(data: &mut &[u8]) -> Result<Self> {
+        if mc_types::get_u8(data)? != LEGACY_PING {
+            return Err(PacketError::InvalidLegacyPing(
+                "unexpected packet id").into());
+        }
+        if data.is_empty() {
+            return Ok(Self::Beta);
+        }
+        if mc_types::get_u8(data)? != LEGACY_PING_PAYLOAD {
+            return Err(PacketError::InvalidLegacyPing(
+                "unexpected ping payload").into());
+        }
+        if data.is_empty() {
+            return Ok(Self::V1_4);
+        }
+        if mc_types::get_u8(data)? != LEGACY_PLUGIN_MESSAGE {
+            return Err(PacketError::InvalidLegacyPing(
+                "unexpected packet id").into());
+        }
+        if get_utf16_string(data)? != PING_HOST_CHANNEL {
+            return Err(PacketError::InvalidLegacyPing(
+                "unexpected plugin channel").into());
+        }
+        let length = mc_types::get_u16(data)? as usize;
+        if length > data.len() {
+            return Err(PacketError::RanOutOfBytes("Legacy Ping").into());
+        }
+        let (mut payload, rest) = data.split_at(length);
+        *data = rest;
+        Ok(Self::V1_6(LegacyPingHost {
+            protocol_version: mc_types::get_u8(&mut payload)?,
+            hostname: get_utf16_string(&mut payload)?,
+            port: mc_types::get_i32(&mut payload)?,
+        }))
+    }
+
+    pub fn convert(&self) -> Vec<u8> {
+        match self {
+            Self::Beta => vec![LEGACY_PING],
+            Self::V1_4 => vec![LEGACY_PING, LEGACY_PING_PAYLOAD],
+            Self::V1_6(host) => {
+                let mut payload = mc_types::convert_u8(host.protocol_version);
+                payload.append(&mut convert_utf16_string(&host.hostname));
+                payload.append(&mut mc_types::convert_i32(host.port));
+
+                let mut data = vec![
+                    LEGACY_PING, LEGACY_PING_PAYLOAD, LEGACY_PLUGIN_MESSAGE];
+                data.append(&mut convert_utf16_string(PING_HOST_CHANNEL));
+                data.append(&mut mc_types::convert_u16(payload.len() as u16));
+                data.append(&mut payload);
+                data
+            },
+        }
+    }
+
+    /// Reads the rest of a legacy ping whose first bytes are in `buffered`.
+    /// Returns `None` if the bytes turn out to be a modern packet, leaving
+    /// everything read so far in `buffered` to be replayed.
+    pub async fn read<R: AsyncRead + Unpin>(
+        stream: &mut R,
+        buffered: &mut Vec<u8>,
+    ) -> Result<Option<Self>> {
+        if buffered.first() != Some(&LEGACY_PING) {
+            return Ok(None);
+        }
+        for expected in [LEGACY_PING_PAYLOAD, LEGACY_PLUGIN_MESSAGE] {
+            match timeout(PAYLOAD_TIMEOUT, stream.read_u8()).await {
+                Ok(byte) => buffered.push(byte?),
+                Err(_) =>
+                    return Ok(Self::get(&mut buffered.as_slice()).ok()),
+            }
+            // A VarInt length of 254 also starts with 0xFE 0x01
+            if buffered[buffered.len() - 1] != expected {
+                return Ok(None);
+            }
+        }
+
+        let length = stream.read_u16().await?;
+        buffered.append(&mut mc_types::convert_u16(length));
+        if length as usize != PING_HOST_CHANNEL.len() {
+            return Ok(None);
+        }
+        let mut channel = vec![0; length as usize * 2];
+        stream.read_exact(&mut channel).await?;
+        buffered.append(&mut channel);
+        let mut channel = &buffered[3..];
+        match get_utf16_string(&mut channel) {
+            Ok(channel) if channel == PING_HOST_CHANNEL => {},
+            _ => return Ok(None),
+        }
+
+        // Past the channel this can only be a ping, so never replay it
+        let length = stream.read_u16().await?;
+        buffered.append(&mut mc_types::convert_u16(length));
+        let mut payload = vec![0; length as usize];
+        stream.read_exact(&mut payload).await?;
+        buffered.append(&mut payload);
+        Self::get(&mut buffered.as_slice())
+            .map(Some)
+            .map_err(|_| PacketError::InvalidLegacyPing(
+                "malformed MC|PingHost payload").into())
+    }
+
+    pub async fn write<W: AsyncWrite + Unpin>(
+        &self,
+        stream: &mut W,
+    ) -> Result<()> {
+        stream.write_all(&self.convert()).await?;
+        Ok(())
+    }
+}
+
+impl LegacyPingResponse {
+    pub fn from_status(data: &StatusResponseData) -> Self {
+        Self {
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            version_name: data.version.name.clone(),
+            motd: data.description.to_legacy(false),
+            online_players: data.players.online,
+            max_players: data.players.max,
+        }
+    }
+
+    pub fn get(data: &mut &[u8]) -> Result<Self> {
+        if mc_types::get_u8(data)? != LEGACY_KICK {
+            return Err(PacketError::InvalidLegacyPing(
+                "unexpected packet id").into());
+        }
+        let reply = get_utf16_string(data)?;
+        let invalid = || PacketError::InvalidLegacyPing(
+            "malformed kick string");
+        match reply.strip_prefix("\u{a7}1\0") {
+            Some(reply) => {
+                let fields: Vec<&str> = reply.split('\0').collect();
+                let [protocol, version, motd, online, max] = fields[..]
+                else {
+                    return Err(invalid().into());
+                };
+                Ok(Self {
+                    protocol_version: protocol.parse()
+                        .map_err(|_| invalid())?,
+                    version_name: version.to_string(),
+                    motd: motd.to_string(),
+                    online_players: online.parse().map_err(|_| invalid())?,
+                    max_players: max.parse().map_err(|_| invalid())?,
+                })
+            },
+            None => {
+                let mut fields = reply.rsplitn(3, text::SECTION_SIGN);
+                let max = fields.next().ok_or_else(invalid)?;
+                let online = fields.next().ok_or_else(invalid)?;
+                let motd = fields.next().ok_or_else(invalid)?;
+                Ok(Self {
+                    protocol_version: LEGACY_PROTOCOL_VERSION,
+                    version_name: String::new(),
+                    motd: motd.to_string(),
+                    online_players: online.parse().map_err(|_| invalid())?,
+                    max_players: max.parse().map_err(|_| invalid())?,
+                })
+            },
+        }
+    }
+
+    /// Encodes the kick string in the format the given ping expects.
+    pub fn convert(&self, ping: &LegacyPing) -> Vec<u8> {
+        let reply = match ping {
+            LegacyPing::Beta => format!(
+                "{}{}{}{}{}",
+                TextComponent::from_legacy(&self.motd)
+                    .to_plain_text()
+                    .replace(text::SECTION_SIGN, ""),
+                text::SECTION_SIGN,
+                self.online_players,
+                text::SECTION_SIGN,
+                self.max_players,
+            ),
+            _ => format!(
+                "\u{a7}1\0{}\0{}\0{}\0{}\0{}",
+                self.protocol_version,
+                self.version_name,
+                self.motd,
+                self.online_players,
+                self.max_players,
+            ),
+        };
+        let mut data = vec![LEGACY_KICK];
+        data.append(&mut convert_utf16_string(&reply));
+        data
+    }
+
+    pub async fn write<W: AsyncWrite + Unpin>(
+        &self,
+        stream: &mut W,
+        ping: &LegacyPing,
+    ) -> Result<()> {
+        stream.write_all(&self.convert(ping)).await?;
+        stream.flush().await?;
+        Ok(())
+    }
+}
+
+fn get_utf16_string(data: &mut &[u8]) -> Result<String> {
+    let length = mc_types::get_u16(data)? as usize;
+    if length * 2 > data.len() {
+        return Err(PacketError::RanOutOfBytes("Legacy String").into());
+    }
+    let mut units = Vec::with_capacity(length);
+    for _ in 0..length {
+        units.push(mc_types::get_u16(data)?);
+    }
+    String::from_utf16(&units).map_err(|_|
+        PacketError::InvalidLegacyPing("invalid UTF-16 string").into())
+}
+
+fn convert_utf16_string(s: &str) -> Vec<u8> {
+    let units: Vec<u16> = s.encode_utf16().collect();
+    let mut data = mc_types::convert_u16(units.len() as u16);
+    for unit in units {
+        data.append(&mut mc_types::convert_u16(unit));
+    }
+    data
+}
@@ -1,8 +1,19 @@
 // Yeahbut December 2023
 
+extern crate self as purple_cello_mc_protocol;
+
 pub mod mc_types;
+pub mod version;
+pub mod text;
+pub mod nbt;
 pub mod handshake;
+pub mod legacy_ping;
 pub mod status;
+pub mod ping;
+pub mod favicon;
 pub mod login;
+pub mod bungeecord;
+pub mod velocity;
+pub mod configuration;
 pub mod encrypt;
 pub mod play;
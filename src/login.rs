@@ -2,442 +2,190 @@
 
 pub mod clientbound {
 
-    use crate::mc_types::{self, Result, Packet, PacketArray, PacketError};
+    use serde::{Serialize, Deserialize};
 
-    pub enum Login {
-        Disconnect(Disconnect),
-        EncryptionRequest(EncryptionRequest),
-        LoginSuccess(LoginSuccess),
-        SetCompression(SetCompression),
-        PluginRequest(PluginRequest),
-        CookieRequest(CookieRequest),
-    }
+    use crate::mc_types::{Packet, PacketArray};
+    use crate::text::TextComponent;
 
-    impl Login {
-        pub async fn read<T: mc_types::ProtocolRead>(
-            conn: &mut T,
-        ) -> Result<Self> {
-            let mut data = conn.read_data().await?;
-            let packet_id = mc_types::get_var_int(&mut data)?;
-            if packet_id == Disconnect::packet_id() {
-                return Ok(Self::Disconnect(Disconnect::get(&mut data)?))
-            } else if packet_id == EncryptionRequest::packet_id() {
-                return Ok(Self::EncryptionRequest(
-                    EncryptionRequest::get(&mut data)?))
-            } else if packet_id == LoginSuccess::packet_id() {
-                return Ok(Self::LoginSuccess(LoginSuccess::get(&mut data)?))
-            } else if packet_id == SetCompression::packet_id() {
-                return Ok(Self::SetCompression(
-                    SetCompression::get(&mut data)?))
-            } else if packet_id == PluginRequest::packet_id() {
-                return Ok(Self::PluginRequest(
-                    PluginRequest::get(&mut data)?))
-            } else if packet_id == CookieRequest::packet_id() {
-                return Ok(Self::CookieRequest(
-                    CookieRequest::get(&mut data)?))
-            } else {
-                return Err(Box::new(PacketError::InvalidPacketId))
-            }
+    crate::packet_enum! {
+        #[allow(clippy::large_enum_variant)]
+        pub enum Login (Login, Clientbound) {
+            Disconnect(Disconnect),
+            EncryptionRequest(EncryptionRequest),
+            LoginSuccess(LoginSuccess),
+            SetCompression(SetCompression),
+            PluginRequest(PluginRequest),
+            CookieRequest(CookieRequest),
         }
     }
 
+    #[derive(Packet)]
+    #[packet(id = 0)]
     pub struct Disconnect {
-        pub reason: String
-    }
-
-    impl Packet for Disconnect {
-
-        fn packet_id() -> i32 {0}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                reason: mc_types::get_string(&mut data)?
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.reason));
-
-            data
-        }
-
+        #[packet(json, max_length = 262144)]
+        pub reason: TextComponent,
     }
 
+    #[derive(Packet)]
+    #[packet(id = 1)]
     pub struct EncryptionRequest {
+        #[packet(max_length = 20)]
         pub server_id: String,
         pub public_key: Vec<u8>,
         pub verify_token: Vec<u8>,
+        #[packet(since = V1_20_5)]
+        pub should_authenticate: bool,
     }
 
-    impl Packet for EncryptionRequest {
-
-        fn packet_id() -> i32 {1}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                server_id: mc_types::get_string(&mut data)?,
-                public_key: mc_types::get_byte_array(&mut data)?,
-                verify_token: mc_types::get_byte_array(&mut data)?,
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.server_id));
-            data.append(&mut mc_types::convert_byte_array(
-                &mut self.public_key.clone()));
-            data.append(&mut mc_types::convert_byte_array(
-                &mut self.verify_token.clone()));
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 2)]
     pub struct LoginSuccess {
         pub uuid: u128,
+        #[packet(max_length = 16)]
         pub username: String,
         pub properties: Vec<LoginSuccessProperty>,
+        #[packet(since = V1_20_5)]
         pub strict_error_handling: bool,
     }
 
-    impl Packet for LoginSuccess {
-
-        fn packet_id() -> i32 {2}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                uuid: mc_types::get_uuid(&mut data),
-                username: mc_types::get_string(&mut data)?,
-                properties: LoginSuccessProperty::get_array(&mut data)?,
-                strict_error_handling: mc_types::get_bool(&mut data),
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_uuid(self.uuid));
-            data.append(&mut mc_types::convert_string(&self.username));
-            data.append(&mut LoginSuccessProperty::convert_array(
-                &mut self.properties.clone()));
-            data.append(&mut mc_types::convert_bool(
-                self.strict_error_handling));
-
-            data
-        }
-
-    }
-
+    #[derive(Debug, Clone, PartialEq, PacketArray, Serialize, Deserialize)]
     pub struct LoginSuccessProperty {
-        name: String,
-        value: String,
-        signature: Option<String>,
-    }
-
-    impl Clone for LoginSuccessProperty {
-        fn clone(&self) -> Self {
-            Self {
-                name: self.name.clone(),
-                value: self.value.clone(),
-                signature: self.signature.clone(),
-            }
-        }
-    }
-
-    impl PacketArray for LoginSuccessProperty {
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            let name = mc_types::get_string(&mut data)?;
-            let value = mc_types::get_string(&mut data)?;
-            let is_signed = mc_types::get_bool(&mut data);
-            let mut signature: Option<String> = None;
-            if is_signed {
-                signature = Some(mc_types::get_string(&mut data)?);
-            }
-            Ok(Self {
-                name,
-                value,
-                signature,
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_string(&self.name));
-            data.append(&mut mc_types::convert_string(&self.value));
-            match &self.signature {
-                Some(value) => {
-                    data.append(&mut &mut mc_types::convert_bool(true));
-                    data.append(&mut mc_types::convert_string(&value));
-                },
-                None => data.append(&mut &mut mc_types::convert_bool(false))
-            }
-
-            data
-        }
-
+        #[packet(max_length = 64)]
+        pub name: String,
+        pub value: String,
+        #[packet(max_length = 1024)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub signature: Option<String>,
     }
 
+    #[derive(Packet)]
+    #[packet(id = 3)]
     pub struct SetCompression {
+        #[packet(var_int)]
         pub threshold: i32,
     }
 
-    impl Packet for SetCompression {
-
-        fn packet_id() -> i32 {3}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                threshold: mc_types::get_var_int(&mut data)?,
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_var_int(self.threshold));
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 4)]
     pub struct PluginRequest {
+        #[packet(var_int)]
         pub message_id: i32,
         pub channel: String,
+        #[packet(remaining)]
         pub data: Vec<u8>,
     }
 
-    impl Packet for PluginRequest {
-
-        fn packet_id() -> i32 {4}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                message_id: mc_types::get_var_int(&mut data)?,
-                channel: mc_types::get_string(&mut data)?,
-                data: data.clone(),
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_var_int(self.message_id));
-            data.append(&mut mc_types::convert_string(&self.channel));
-            data.append(&mut self.data.clone());
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 5, since = V1_20_5)]
     pub struct CookieRequest {
         pub key: String,
     }
 
-    impl Packet for CookieRequest {
-
-        fn packet_id() -> i32 {5}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                key: mc_types::get_string(&mut data)?,
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.key));
-
-            data
-        }
-
-    }
-
 }
 
 pub mod serverbound {
 
-    use crate::mc_types::{self, Result, Packet, PacketError};
-
-    pub enum Login {
-        LoginStart(LoginStart),
-        EncryptionResponse(EncryptionResponse),
-        PluginResponse(PluginResponse),
-        Acknowledged(Acknowledged),
-        CookieResponse(CookieResponse),
-    }
+    use crate::mc_types::{self, Packet, Result, ResultContext};
+    use crate::version::ProtocolVersion;
 
-    impl Login {
-        pub async fn read<T: mc_types::ProtocolRead>(
-            conn: &mut T,
-        ) -> Result<Self> {
-            let mut data = conn.read_data().await?;
-            let packet_id = mc_types::get_var_int(&mut data)?;
-            if packet_id == LoginStart::packet_id() {
-                return Ok(Self::LoginStart(LoginStart::get(&mut data)?))
-            } else if packet_id == EncryptionResponse::packet_id() {
-                return Ok(Self::EncryptionResponse(
-                    EncryptionResponse::get(&mut data)?))
-            } else if packet_id == PluginResponse::packet_id() {
-                return Ok(Self::PluginResponse(
-                    PluginResponse::get(&mut data)?))
-            } else if packet_id == Acknowledged::packet_id() {
-                return Ok(Self::Acknowledged(
-                    Acknowledged::get(&mut data)?))
-            } else if packet_id == CookieResponse::packet_id() {
-                return Ok(Self::CookieResponse(
-                    CookieResponse::get(&mut data)?))
-            } else {
-                return Err(Box::new(PacketError::InvalidPacketId))
-            }
+    crate::packet_enum! {
+        pub enum Login (Login, Serverbound) {
+            LoginStart(LoginStart),
+            EncryptionResponse(EncryptionResponse),
+            PluginResponse(PluginResponse),
+            Acknowledged(Acknowledged),
+            CookieResponse(CookieResponse),
         }
     }
 
     pub struct LoginStart {
         pub name: String,
+        // Clients before 1.20.2 may leave the UUID out
+        pub has_player_uuid: bool,
         pub player_uuid: u128,
     }
 
     impl Packet for LoginStart {
 
-        fn packet_id() -> i32 {0}
+        const PACKET_ID: i32 = 0;
 
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            let name = mc_types::get_string(&mut data)?;
-            let player_uuid: u128 = mc_types::get_uuid(&mut data);
-            Ok(Self {
-                name,
-                player_uuid,
-            })
+        fn get(data: &mut &[u8]) -> Result<Self> {
+            Self::get_versioned(data, ProtocolVersion::LATEST)
         }
 
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.name));
-            data.append(&mut mc_types::convert_uuid(self.player_uuid));
+        fn convert(&self) -> Result<Vec<u8>> {
+            Ok(self.convert_fields(ProtocolVersion::LATEST))
+        }
 
-            data
+        fn get_versioned(
+            data: &mut &[u8],
+            version: ProtocolVersion,
+        ) -> Result<Self> {
+            let name = mc_types::get_string_limited(
+                data, mc_types::MAX_USERNAME_LENGTH).field("name")?;
+            let has_player_uuid = version >= ProtocolVersion::V1_20_2 ||
+                mc_types::get_bool(data).field("has_player_uuid")?;
+            let player_uuid = if has_player_uuid {
+                mc_types::get_uuid(data).field("player_uuid")?
+            } else {
+                0
+            };
+            Ok(Self { name, has_player_uuid, player_uuid })
         }
 
-    }
+        fn convert_versioned(
+            &self,
+            version: ProtocolVersion,
+        ) -> Result<Vec<u8>> {
+            Ok(self.convert_fields(version))
+        }
 
-    pub struct EncryptionResponse {
-        pub shared_secret: Vec<u8>,
-        pub verify_token: Vec<u8>,
     }
 
-    impl Packet for EncryptionResponse {
-
-        fn packet_id() -> i32 {1}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                shared_secret: mc_types::get_byte_array(&mut data)?,
-                verify_token: mc_types::get_byte_array(&mut data)?,
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
+    impl LoginStart {
+        fn convert_fields(&self, version: ProtocolVersion) -> Vec<u8> {
             let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_byte_array(
-                &mut self.shared_secret.clone()));
-            data.append(&mut mc_types::convert_byte_array(
-                &mut self.verify_token.clone()));
+            data.append(&mut mc_types::convert_var_int(Self::PACKET_ID));
+            data.append(&mut mc_types::convert_string(&self.name));
+            if version < ProtocolVersion::V1_20_2 {
+                data.append(&mut mc_types::convert_bool(self.has_player_uuid));
+                if !self.has_player_uuid {
+                    return data;
+                }
+            }
+            data.append(&mut mc_types::convert_uuid(self.player_uuid));
 
             data
         }
+    }
 
+    #[derive(Packet)]
+    #[packet(id = 1)]
+    pub struct EncryptionResponse {
+        pub shared_secret: Vec<u8>,
+        pub verify_token: Vec<u8>,
     }
 
+    #[derive(Packet)]
+    #[packet(id = 2)]
     pub struct PluginResponse {
+        #[packet(var_int)]
         pub message_id: i32,
         pub successful: bool,
+        #[packet(remaining)]
         pub data: Vec<u8>,
     }
 
-    impl Packet for PluginResponse {
-
-        fn packet_id() -> i32 {2}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                message_id: mc_types::get_var_int(&mut data)?,
-                successful: mc_types::get_bool(&mut data),
-                data: data.clone(),
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_var_int(self.message_id));
-            data.append(&mut mc_types::convert_bool(self.successful));
-            data.append(&mut self.data.clone());
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 3, since = V1_20_2)]
     pub struct Acknowledged {}
 
-    impl Packet for Acknowledged {
-
-        fn packet_id() -> i32 {3}
-
-        fn get(_data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {})
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 4, since = V1_20_5)]
     pub struct CookieResponse {
         pub key: String,
-        pub has_payload: bool,
-        pub payload_load: i32,
-        pub payload: Vec<u8>,
-    }
-
-    impl Packet for CookieResponse {
-
-        fn packet_id() -> i32 {4}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                key: mc_types::get_string(&mut data)?,
-                has_payload: mc_types::get_bool(&mut data),
-                payload_load: mc_types::get_var_int(&mut data)?,
-                payload: data.clone(),
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.key));
-            data.append(&mut mc_types::convert_bool(self.has_payload));
-            data.append(&mut mc_types::convert_var_int(self.payload_load));
-            data.append(&mut self.payload.clone());
-
-            data
-        }
-
+        #[packet(max_length = 5120)]
+        pub payload: Option<Vec<u8>>,
     }
 
 }
@@ -1,11 +1,13 @@
 // Yeahbut December 2023
 
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
+use std::io::{Read, Write};
 
+use tokio::net::TcpStream;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use async_trait::async_trait;
 use rsa::{RsaPrivateKey, RsaPublicKey};
 use rsa::pkcs8::{EncodePublicKey, DecodePublicKey};
@@ -13,26 +15,100 @@ use rand::Rng;
 use crypto::digest::Digest;
 use sha1::Sha1;
 use num_bigint::BigInt;
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
 
 use crate::login;
+use crate::handshake::serverbound::{HandshakeEnum, HandshakeStart};
+use crate::legacy_ping::{self, LegacyPing};
 use crate::encrypt::{self, McCipher};
-use crate::play::Play;
+use crate::version::{
+    self, ProtocolVersion, SUPPORTED_VERSIONS, VERSION_COUNT};
+use crate::text::TextComponent;
+use crate::nbt::{self, Tag};
 
-pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+pub use purple_cello_mc_protocol_derive::{Packet, PacketArray};
 
-pub const VERSION_NAME: &str = "1.21";
-pub const VERSION_PROTOCOL: i32 = 767;
+pub type Result<T> = std::result::Result<T, ProtocolError>;
+
+pub const VERSION_NAME: &str = ProtocolVersion::LATEST.names()[0];
+pub const VERSION_PROTOCOL: i32 = ProtocolVersion::LATEST.protocol();
+
+pub const MAX_PACKET_LENGTH: usize = 2097151;
+pub const MAX_UNCOMPRESSED_LENGTH: usize = 8388608;
+pub const MAX_STRING_LENGTH: usize = 32767;
+pub const MAX_USERNAME_LENGTH: usize = 16;
+pub const MAX_COMPONENT_LENGTH: usize = 262144;
+
+const MAX_PACKET_LENGTH_BYTES: usize = 3;
 
 const SEGMENT_BITS: u8 = 0x7F;
 const CONTINUE_BIT: u8 = 0x80;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Handshake,
+    Status,
+    Login,
+    Configuration,
+    Play,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Clientbound,
+    Serverbound,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PacketContext {
+    pub state: Option<ConnectionState>,
+    pub direction: Option<Direction>,
+    pub packet_id: Option<i32>,
+    pub field: Option<&'static str>,
+}
+
+impl fmt::Display for PacketContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(state) = self.state {
+            write!(f, " in {:?}", state)?;
+        }
+        if let Some(direction) = self.direction {
+            write!(f, " {:?}", direction)?;
+        }
+        if let Some(packet_id) = self.packet_id {
+            write!(f, " packet 0x{:02X}", packet_id)?;
+        }
+        if let Some(field) = self.field {
+            write!(f, " field {}", field)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum PacketError {
     ValueTooLarge,
-    RanOutOfBytes,
-    InvalidPacketId,
+    RanOutOfBytes(&'static str),
+    InvalidPacketId(i32),
+    UnavailableInVersion(ProtocolVersion),
     InvalidUUIDString,
-    EncryptionError,
+    InvalidJson(serde_json::Error),
+    InvalidNbt(&'static str),
+    InvalidLegacyPing(&'static str),
+    InvalidFavicon(&'static str),
+    InvalidNextState(i32),
+    InvalidForwarding(&'static str),
+    PongMismatch {
+        expected: i64,
+        received: i64,
+    },
+    LengthExceeded {
+        field: &'static str,
+        length: usize,
+        max: usize,
+    },
 }
 
 impl fmt::Display for PacketError {
@@ -40,25 +116,163 @@ impl fmt::Display for PacketError {
         match self {
             PacketError::ValueTooLarge =>
                 write!(f, "VarInt value is too large"),
-            PacketError::RanOutOfBytes =>
-                write!(f, "Ran out of bytes while reading VarInt"),
-            PacketError::InvalidPacketId =>
-                write!(f, "Invalid packet id"),
+            PacketError::RanOutOfBytes(field) =>
+                write!(f, "Ran out of bytes while reading {}", field),
+            PacketError::InvalidPacketId(packet_id) =>
+                write!(f, "Invalid packet id 0x{:02X}", packet_id),
+            PacketError::UnavailableInVersion(version) =>
+                write!(f, "Packet is not available in version {}", version),
             PacketError::InvalidUUIDString =>
                 write!(f, "Invalid UUID format"),
-            PacketError::EncryptionError =>
-                write!(f, "Encryption Error"),
+            PacketError::InvalidJson(error) =>
+                write!(f, "Invalid JSON: {}", error),
+            PacketError::InvalidNbt(reason) =>
+                write!(f, "Invalid NBT: {}", reason),
+            PacketError::InvalidLegacyPing(reason) =>
+                write!(f, "Invalid legacy ping: {}", reason),
+            PacketError::InvalidFavicon(reason) =>
+                write!(f, "Invalid favicon: {}", reason),
+            PacketError::InvalidNextState(next_state) =>
+                write!(f, "Invalid handshake next state {}", next_state),
+            PacketError::InvalidForwarding(reason) =>
+                write!(f, "Invalid player info forwarding: {}", reason),
+            PacketError::PongMismatch { expected, received } =>
+                write!(f, "Pong payload {} does not match ping {}",
+                    received, expected),
+            PacketError::LengthExceeded { field, length, max } =>
+                write!(f, "{} length {} exceeds maximum of {}",
+                    field, length, max),
         }
     }
 }
 
 impl Error for PacketError {}
 
-#[derive(Serialize, Deserialize)]
-pub struct Chat {
-    pub text: String,
+#[derive(Debug)]
+pub enum ProtocolError {
+    Io(std::io::Error),
+    Framing(PacketError),
+    Decode(PacketError, PacketContext),
+    Encryption(String),
+    Compression(String),
+    Authentication(String),
+    UnsupportedVersion(i32),
 }
 
+impl ProtocolError {
+    pub fn context(&self) -> Option<&PacketContext> {
+        match self {
+            ProtocolError::Decode(_, context) => Some(context),
+            _ => None,
+        }
+    }
+
+    pub fn with_field(mut self, field: &'static str) -> Self {
+        if let ProtocolError::Decode(_, context) = &mut self {
+            context.field.get_or_insert(field);
+        }
+        self
+    }
+
+    pub fn with_packet_id(mut self, packet_id: i32) -> Self {
+        if let ProtocolError::Decode(_, context) = &mut self {
+            context.packet_id.get_or_insert(packet_id);
+        }
+        self
+    }
+
+    pub fn with_state(
+        mut self,
+        state: ConnectionState,
+        direction: Direction,
+    ) -> Self {
+        if let ProtocolError::Decode(_, context) = &mut self {
+            context.state.get_or_insert(state);
+            context.direction.get_or_insert(direction);
+        }
+        self
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Io(error) =>
+                write!(f, "I/O error: {}", error),
+            ProtocolError::Framing(error) =>
+                write!(f, "Framing error: {}", error),
+            ProtocolError::Decode(error, context) =>
+                write!(f, "Decode error{}: {}", context, error),
+            ProtocolError::Encryption(reason) =>
+                write!(f, "Encryption error: {}", reason),
+            ProtocolError::Compression(reason) =>
+                write!(f, "Compression error: {}", reason),
+            ProtocolError::Authentication(reason) =>
+                write!(f, "Authentication error: {}", reason),
+            ProtocolError::UnsupportedVersion(protocol) =>
+                write!(f, "Unsupported protocol version {} (supported: {})",
+                    protocol,
+                    version::SUPPORTED_VERSIONS.iter()
+                        .map(|version| version.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")),
+        }
+    }
+}
+
+impl Error for ProtocolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProtocolError::Io(error) => Some(error),
+            ProtocolError::Framing(error) => Some(error),
+            ProtocolError::Decode(error, _) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<PacketError> for ProtocolError {
+    fn from(error: PacketError) -> Self {
+        ProtocolError::Decode(error, PacketContext::default())
+    }
+}
+
+impl From<std::io::Error> for ProtocolError {
+    fn from(error: std::io::Error) -> Self {
+        ProtocolError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ProtocolError {
+    fn from(error: serde_json::Error) -> Self {
+        PacketError::InvalidJson(error).into()
+    }
+}
+
+impl From<rsa::errors::Error> for ProtocolError {
+    fn from(error: rsa::errors::Error) -> Self {
+        ProtocolError::Encryption(error.to_string())
+    }
+}
+
+impl From<rsa::pkcs8::spki::Error> for ProtocolError {
+    fn from(error: rsa::pkcs8::spki::Error) -> Self {
+        ProtocolError::Encryption(error.to_string())
+    }
+}
+
+pub trait ResultContext {
+    fn field(self, field: &'static str) -> Self;
+}
+
+impl<T> ResultContext for Result<T> {
+    fn field(self, field: &'static str) -> Self {
+        self.map_err(|error| error.with_field(field))
+    }
+}
+
+pub type Chat = TextComponent;
+
 #[async_trait]
 pub trait ProtocolRead {
     async fn read_data(&mut self) -> Result<Vec<u8>>;
@@ -69,20 +283,33 @@ pub trait ProtocolWrite {
     async fn write_data(&mut self, data: &mut Vec<u8>) -> Result<()>;
 }
 
-pub struct ProtocolConnection<'a> {
-    pub stream_read: &'a mut OwnedReadHalf,
-    pub stream_write: &'a mut OwnedWriteHalf,
+pub struct ProtocolConnection<R = OwnedReadHalf, W = OwnedWriteHalf> {
+    pub stream_read: R,
+    pub stream_write: W,
     rsa_private_key: Option<RsaPrivateKey>,
     rsa_public_key: Option<RsaPublicKey>,
     aes_cipher: Option<McCipher>,
     verify_token: Option<[u8; 16]>,
-    server_id: String
+    server_id: String,
+    compression_threshold: Option<usize>,
+    max_packet_length: usize,
 }
 
-impl<'a> ProtocolConnection<'a> {
+impl ProtocolConnection {
+    pub fn from_stream(stream: TcpStream) -> Self {
+        let (stream_read, stream_write) = stream.into_split();
+        Self::new(stream_read, stream_write)
+    }
+}
+
+impl<R, W> ProtocolConnection<R, W>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
     pub fn new(
-        stream_read: &'a mut OwnedReadHalf,
-        stream_write: &'a mut OwnedWriteHalf,
+        stream_read: R,
+        stream_write: W,
     ) -> Self {
         ProtocolConnection {
             stream_read,
@@ -92,9 +319,23 @@ impl<'a> ProtocolConnection<'a> {
             aes_cipher: None,
             verify_token: None,
             server_id: "".to_string(),
+            compression_threshold: None,
+            max_packet_length: MAX_PACKET_LENGTH,
         }
     }
 
+    pub fn set_compression(&mut self, threshold: i32) {
+        self.compression_threshold = compression_threshold(threshold);
+    }
+
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    pub fn set_max_packet_length(&mut self, length: usize) {
+        self.max_packet_length = length;
+    }
+
     pub fn create_encryption_request(
         &mut self,
         private_key: RsaPrivateKey,
@@ -120,11 +361,14 @@ impl<'a> ProtocolConnection<'a> {
                                 .as_ref()
                                 .to_vec(),
                             verify_token: token[0..16].to_vec(),
+                            should_authenticate: true,
                         }),
-                    None => Err(Box::new(PacketError::EncryptionError))
+                    None => Err(ProtocolError::Encryption(
+                        "verify token not generated".to_string()))
                 }
             },
-            None => Err(Box::new(PacketError::EncryptionError))
+            None => Err(ProtocolError::Encryption(
+                "public key not generated".to_string()))
         }
     }
 
@@ -132,6 +376,11 @@ impl<'a> ProtocolConnection<'a> {
         &mut self,
         request: login::clientbound::EncryptionRequest,
     ) -> Result<login::serverbound::EncryptionResponse> {
+        let verify_token: &[u8; 16] = request.verify_token
+            .as_slice()
+            .try_into()
+            .map_err(|_| ProtocolError::Encryption(
+                "verify token must be 16 bytes".to_string()))?;
         self.server_id = request.server_id;
         self.rsa_public_key = Some(
             RsaPublicKey::from_public_key_der(&request.public_key)?);
@@ -145,16 +394,16 @@ impl<'a> ProtocolConnection<'a> {
                                 .get_encrypted_key(public_key)?,
                             verify_token: encrypt::encrypt_rsa(
                                 public_key,
-                                request.verify_token[0..16]
-                                    .try_into()
-                                    .unwrap(),
+                                verify_token,
                             )?,
                         })
                     },
-                    None => Err(Box::new(PacketError::EncryptionError))
+                    None => Err(ProtocolError::Encryption(
+                        "missing server public key".to_string()))
                 }
             },
-            None => Err(Box::new(PacketError::EncryptionError))
+            None => Err(ProtocolError::Encryption(
+                "missing shared secret".to_string()))
         }
     }
 
@@ -166,8 +415,8 @@ impl<'a> ProtocolConnection<'a> {
             Some (token) => {
                 match &self.rsa_private_key {
                     Some (private_key) => {
-                        if &encrypt::decrypt_rsa(
-                            &private_key,
+                        if encrypt::decrypt_rsa(
+                            private_key,
                             response.verify_token.as_slice()
                         )? == token {
                             self.aes_cipher =
@@ -177,38 +426,72 @@ impl<'a> ProtocolConnection<'a> {
                                 )?);
                             Ok(())
                         } else {
-                            Err(Box::new(PacketError::EncryptionError))
+                            Err(ProtocolError::Authentication(
+                                "verify token mismatch".to_string()))
                         }
                     }
-                    None => Err(Box::new(PacketError::EncryptionError))
+                    None => Err(ProtocolError::Encryption(
+                        "private key not set".to_string()))
                 }
             }
-            None => Err(Box::new(PacketError::EncryptionError))
+            None => Err(ProtocolError::Encryption(
+                "encryption request not sent".to_string()))
+        }
+    }
+
+    /// Reads the first packet of a connection, which is either a handshake
+    /// or a pre-1.7 legacy ping.
+    pub async fn read_handshake(&mut self) -> Result<HandshakeStart> {
+        let mut buffered = vec![self.stream_read.read_u8().await?];
+        if legacy_ping::is_legacy_ping(buffered[0]) {
+            if let Some(ping) =
+                LegacyPing::read(&mut self.stream_read, &mut buffered).await?
+            {
+                return Ok(HandshakeStart::LegacyPing(ping));
+            }
+        }
+        // Replay whatever the legacy ping check consumed
+        let mut stream =
+            AsyncReadExt::chain(&buffered[1..], &mut self.stream_read);
+        let length = continue_var_int_stream(&mut stream, buffered[0]).await?;
+        let length = check_packet_length(length, self.max_packet_length)?;
+
+        let mut buffer: Vec<u8> = vec![0; length];
+        stream.read_exact(&mut buffer).await?;
+        let mut data = buffer.as_slice();
+        let packet_id = get_var_int(&mut data)?;
+        match HandshakeEnum::get(packet_id, &mut data) {
+            Ok(HandshakeEnum::Handshake(handshake)) =>
+                Ok(HandshakeStart::Handshake(handshake)),
+            Err(error) => Err(error
+                .with_packet_id(packet_id)
+                .with_state(HandshakeEnum::STATE, HandshakeEnum::DIRECTION)),
         }
     }
 
-    pub async fn forward_play(
+    pub async fn forward_play<T: ProtocolWrite + Send>(
         &mut self,
-        other: &mut ProtocolConnection<'_>,
+        other: &mut T,
     ) -> Result<()> {
         loop {
-            let packet = Play::read(self).await?;
-            match packet {
-                Play::PlayPacket(packet) => packet.write(other).await?,
-            };
+            let mut data = self.read_data().await?;
+            other.write_data(&mut data).await?;
         }
     }
 
     pub fn split_conn(
-        &mut self
-    ) -> Result<(WriteHaftProtocolConnection, ReadHaftProtocolConnection)> {
+        self
+    ) -> Result<(WriteHaftProtocolConnection<W>, ReadHaftProtocolConnection<R>)> {
         Ok((WriteHaftProtocolConnection {
-            stream_write: &mut self.stream_write,
+            stream_write: self.stream_write,
             aes_cipher: self.aes_cipher.clone(),
+            compression_threshold: self.compression_threshold,
         },
         ReadHaftProtocolConnection {
-            stream_read: &mut self.stream_read,
-            aes_cipher: self.aes_cipher.clone(),
+            stream_read: self.stream_read,
+            aes_cipher: self.aes_cipher,
+            compression_threshold: self.compression_threshold,
+            max_packet_length: self.max_packet_length,
         }))
     }
 
@@ -220,9 +503,11 @@ impl<'a> ProtocolConnection<'a> {
                     &aes_cipher.key,
                     key.to_public_key_der()?.as_ref(),
                 ].concat(),
-                None => return Err(Box::new(PacketError::EncryptionError))
+                None => return Err(ProtocolError::Encryption(
+                    "missing public key".to_string()))
             },
-            None => return Err(Box::new(PacketError::EncryptionError))
+            None => return Err(ProtocolError::Encryption(
+                "encryption not enabled".to_string()))
         };
         let hash = BigInt::from_signed_bytes_be(
             &Sha1::digest(hash_data)).to_str_radix(16);
@@ -230,188 +515,605 @@ impl<'a> ProtocolConnection<'a> {
     }
 }
 
-unsafe impl<'a> Send for ProtocolConnection<'a> {}
-
 #[async_trait]
-impl<'a> ProtocolRead for ProtocolConnection<'a> {
+impl<R, W> ProtocolRead for ProtocolConnection<R, W>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
     async fn read_data(&mut self) -> Result<Vec<u8>> {
-        match &mut self.aes_cipher {
-            Some(aes_cipher) => {
-                let length = read_var_int_stream_encrypted(
-                    self.stream_read, aes_cipher).await? as usize;
-
-                let mut buffer: Vec<u8> = vec![0; length];
-                self.stream_read.read_exact(&mut buffer).await?;
-                Ok(aes_cipher.decrypt_aes(buffer))
-            },
-            None => {
-                let length = read_var_int_stream(
-                    self.stream_read).await? as usize;
-
-                let mut buffer: Vec<u8> = vec![0; length];
-                self.stream_read.read_exact(&mut buffer).await?;
-
-                Ok(buffer)
-            }
-        }
+        read_frame(
+            &mut self.stream_read,
+            &mut self.aes_cipher,
+            self.compression_threshold,
+            self.max_packet_length,
+        ).await
     }
 }
 
 #[async_trait]
-impl<'a> ProtocolWrite for ProtocolConnection<'a> {
+impl<R, W> ProtocolWrite for ProtocolConnection<R, W>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
     async fn write_data(&mut self, data: &mut Vec<u8>) -> Result<()> {
-        let mut out_data = convert_var_int(data.len() as i32);
-        out_data.append(data);
-        match &mut self.aes_cipher {
-            Some(aes_cipher) => {
-                self.stream_write.write_all(
-                    &aes_cipher.encrypt_aes(out_data)).await?;
-
-                Ok(())
-            },
-            None => {
-                self.stream_write.write_all(&out_data).await?;
-
-                Ok(())
-            }
-        }
+        write_frame(
+            &mut self.stream_write,
+            &mut self.aes_cipher,
+            self.compression_threshold,
+            data,
+        ).await
     }
 }
 
-pub struct WriteHaftProtocolConnection<'a> {
-    pub stream_write: &'a mut OwnedWriteHalf,
+pub struct WriteHaftProtocolConnection<W = OwnedWriteHalf> {
+    pub stream_write: W,
     aes_cipher: Option<McCipher>,
+    compression_threshold: Option<usize>,
 }
 
-impl<'a> WriteHaftProtocolConnection<'a> {
+impl<W: AsyncWrite + Unpin + Send> WriteHaftProtocolConnection<W> {
     pub fn new(
-        stream_write: &'a mut OwnedWriteHalf,
+        stream_write: W,
     ) -> Self {
         WriteHaftProtocolConnection {
             stream_write,
             aes_cipher: None,
+            compression_threshold: None,
         }
     }
-}
 
-unsafe impl<'a> Send for WriteHaftProtocolConnection<'a> {}
+    pub fn set_compression(&mut self, threshold: i32) {
+        self.compression_threshold = compression_threshold(threshold);
+    }
+
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+}
 
 #[async_trait]
-impl<'a> ProtocolWrite for WriteHaftProtocolConnection<'a> {
+impl<W> ProtocolWrite for WriteHaftProtocolConnection<W>
+where
+    W: AsyncWrite + Unpin + Send,
+{
     async fn write_data(&mut self, data: &mut Vec<u8>) -> Result<()> {
-        let mut out_data = convert_var_int(data.len() as i32);
-        out_data.append(data);
-        match &mut self.aes_cipher {
-            Some(aes_cipher) => {
-                self.stream_write.write_all(
-                    &aes_cipher.encrypt_aes(out_data)).await?;
-
-                Ok(())
-            },
-            None => {
-                self.stream_write.write_all(&out_data).await?;
-
-                Ok(())
-            }
-        }
+        write_frame(
+            &mut self.stream_write,
+            &mut self.aes_cipher,
+            self.compression_threshold,
+            data,
+        ).await
     }
 }
 
-pub struct ReadHaftProtocolConnection<'a> {
-    pub stream_read: &'a mut OwnedReadHalf,
+pub struct ReadHaftProtocolConnection<R = OwnedReadHalf> {
+    pub stream_read: R,
     aes_cipher: Option<McCipher>,
+    compression_threshold: Option<usize>,
+    max_packet_length: usize,
 }
 
-impl<'a> ReadHaftProtocolConnection<'a> {
+impl<R: AsyncRead + Unpin + Send> ReadHaftProtocolConnection<R> {
     pub fn new(
-        stream_read: &'a mut OwnedReadHalf,
+        stream_read: R,
     ) -> Self {
         ReadHaftProtocolConnection {
             stream_read,
             aes_cipher: None,
+            compression_threshold: None,
+            max_packet_length: MAX_PACKET_LENGTH,
         }
     }
 
+    pub fn set_compression(&mut self, threshold: i32) {
+        self.compression_threshold = compression_threshold(threshold);
+    }
+
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    pub fn set_max_packet_length(&mut self, length: usize) {
+        self.max_packet_length = length;
+    }
+
     pub async fn forward_play<T: ProtocolWrite + Send>(
         &mut self,
         other: &mut T,
     ) -> Result<()> {
         loop {
-            let packet = Play::read(self).await?;
-            match packet {
-                Play::PlayPacket(packet) => packet.write(other).await?,
-            };
+            let mut data = self.read_data().await?;
+            other.write_data(&mut data).await?;
         }
     }
 }
 
-unsafe impl<'a> Send for ReadHaftProtocolConnection<'a> {}
-
 #[async_trait]
-impl<'a> ProtocolRead for ReadHaftProtocolConnection<'a> {
+impl<R> ProtocolRead for ReadHaftProtocolConnection<R>
+where
+    R: AsyncRead + Unpin + Send,
+{
     async fn read_data(&mut self) -> Result<Vec<u8>> {
-        match &mut self.aes_cipher {
-            Some(aes_cipher) => {
-                let length = read_var_int_stream_encrypted(
-                    self.stream_read, aes_cipher).await? as usize;
-
-                let mut buffer: Vec<u8> = vec![0; length];
-                self.stream_read.read_exact(&mut buffer).await?;
-                Ok(aes_cipher.decrypt_aes(buffer))
-            },
-            None => {
-                let length = read_var_int_stream(
-                    self.stream_read).await? as usize;
-
-                let mut buffer: Vec<u8> = vec![0; length];
-                self.stream_read.read_exact(&mut buffer).await?;
-
-                Ok(buffer)
-            }
-        }
+        read_frame(
+            &mut self.stream_read,
+            &mut self.aes_cipher,
+            self.compression_threshold,
+            self.max_packet_length,
+        ).await
     }
 }
 
 #[async_trait]
 pub trait Packet: Sized {
-    fn packet_id() -> i32;
-    fn get(data: &mut Vec<u8>) -> Result<Self>;
-    fn convert(&self) -> Vec<u8>;
+    const PACKET_ID: i32;
+
+    fn packet_id() -> i32 {
+        Self::PACKET_ID
+    }
+    fn get(data: &mut &[u8]) -> Result<Self>;
+    fn convert(&self) -> Result<Vec<u8>>;
+
+    fn get_consumed(data: &[u8]) -> Result<(Self, usize)> {
+        let mut cursor = data;
+        let packet = Self::get(&mut cursor)?;
+        Ok((packet, data.len() - cursor.len()))
+    }
 
     async fn read<T: ProtocolRead + Send>(conn: &mut T) -> Result<Self> {
-        let mut data = conn.read_data().await?;
+        let data = conn.read_data().await?;
+        let mut data = data.as_slice();
         let packet_id = get_var_int(&mut data)?;
         if packet_id == Self::packet_id() {
-            return Ok(Self::get(&mut data)?)
+            Self::get(&mut data)
+                .map_err(|error| error.with_packet_id(packet_id))
         } else {
-            return Err(Box::new(PacketError::InvalidPacketId))
+            Err(PacketError::InvalidPacketId(packet_id).into())
         }
     }
 
     async fn write<T: ProtocolWrite + Send>(&self, conn: &mut T) -> Result<()> {
-        conn.write_data(&mut self.convert()).await
+        conn.write_data(&mut self.convert()?).await
+    }
+
+    /// The id in each of `SUPPORTED_VERSIONS`, `None` where the packet
+    /// is unavailable.
+    const VERSIONED_IDS: [Option<i32>; VERSION_COUNT] =
+        versioned_ids(Self::PACKET_ID, None, None, &[]);
+
+    fn packet_id_for(version: ProtocolVersion) -> Option<i32> {
+        Self::VERSIONED_IDS[version.index()]
+    }
+
+    fn get_versioned(
+        data: &mut &[u8],
+        _version: ProtocolVersion,
+    ) -> Result<Self> {
+        Self::get(data)
+    }
+
+    fn convert_versioned(&self, _version: ProtocolVersion) -> Result<Vec<u8>> {
+        self.convert()
+    }
+
+    async fn read_versioned<T: ProtocolRead + Send>(
+        conn: &mut T,
+        version: ProtocolVersion,
+    ) -> Result<Self> {
+        let data = conn.read_data().await?;
+        let mut data = data.as_slice();
+        let packet_id = get_var_int(&mut data)?;
+        if Some(packet_id) == Self::packet_id_for(version) {
+            Self::get_versioned(&mut data, version)
+                .map_err(|error| error.with_packet_id(packet_id))
+        } else {
+            Err(PacketError::InvalidPacketId(packet_id).into())
+        }
+    }
+
+    async fn write_versioned<T: ProtocolWrite + Send>(
+        &self,
+        conn: &mut T,
+        version: ProtocolVersion,
+    ) -> Result<()> {
+        conn.write_data(&mut self.convert_versioned(version)?).await
     }
 }
 
-async fn read_var_int_stream(stream: &mut OwnedReadHalf) -> Result<i32> {
-    let mut data: Vec<u8> = vec![];
+pub const fn versioned_ids(
+    packet_id: i32,
+    since: Option<ProtocolVersion>,
+    until: Option<ProtocolVersion>,
+    overrides: &[(ProtocolVersion, i32)],
+) -> [Option<i32>; VERSION_COUNT] {
+    let mut ids = [None; VERSION_COUNT];
+    let mut i = 0;
+    while i < VERSION_COUNT {
+        let protocol = SUPPORTED_VERSIONS[i].protocol();
+        let mut available = true;
+        if let Some(since) = since {
+            available = available && protocol >= since.protocol();
+        }
+        if let Some(until) = until {
+            available = available && protocol <= until.protocol();
+        }
+        if available {
+            let mut id = packet_id;
+            let mut j = 0;
+            while j < overrides.len() {
+                if overrides[j].0.protocol() == protocol {
+                    id = overrides[j].1;
+                }
+                j += 1;
+            }
+            ids[i] = Some(id);
+        }
+        i += 1;
+    }
+    ids
+}
 
-    loop {
-        let current_byte = stream.read_u8().await?;
+pub const fn assert_unique_packet_ids(packet_ids: &[Option<i32>]) {
+    let mut i = 0;
+    while i < packet_ids.len() {
+        let mut j = i + 1;
+        while j < packet_ids.len() {
+            if let (Some(a), Some(b)) = (packet_ids[i], packet_ids[j]) {
+                if a == b {
+                    panic!("duplicate packet id in packet enum");
+                }
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
 
-        data.append(&mut vec![current_byte]);
+#[macro_export]
+macro_rules! packet_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident ($state:ident, $direction:ident) {
+            $($variant:ident($packet:ty)),* $(,)?
+            $(_ => $unknown:ident $(,)?)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant($packet),)*
+            $($unknown { id: i32, data: Vec<u8> },)?
+        }
 
-        if (current_byte & CONTINUE_BIT) == 0 {
-            break;
+        const _: () = {
+            let mut i = 0;
+            while i < $crate::version::VERSION_COUNT {
+                $crate::mc_types::assert_unique_packet_ids(&[
+                    $(<$packet as $crate::mc_types::Packet>
+                        ::VERSIONED_IDS[i],)*
+                ]);
+                i += 1;
+            }
+        };
+
+        impl $name {
+            pub const STATE: $crate::mc_types::ConnectionState =
+                $crate::mc_types::ConnectionState::$state;
+            pub const DIRECTION: $crate::mc_types::Direction =
+                $crate::mc_types::Direction::$direction;
+            pub const PACKET_IDS: &'static [i32] = &[
+                $(<$packet as $crate::mc_types::Packet>::PACKET_ID,)*
+            ];
+
+            pub async fn read<T: $crate::mc_types::ProtocolRead>(
+                conn: &mut T,
+            ) -> $crate::mc_types::Result<Self> {
+                let data = conn.read_data().await?;
+                let mut data = data.as_slice();
+                let packet_id = $crate::mc_types::get_var_int(&mut data)?;
+                Self::get(packet_id, &mut data).map_err(|error| error
+                    .with_packet_id(packet_id)
+                    .with_state(Self::STATE, Self::DIRECTION))
+            }
+
+            #[allow(unreachable_code)]
+            pub fn get(
+                packet_id: i32,
+                data: &mut &[u8],
+            ) -> $crate::mc_types::Result<Self> {
+                $(
+                    if packet_id ==
+                        <$packet as $crate::mc_types::Packet>::PACKET_ID
+                    {
+                        return Ok(Self::$variant(
+                            <$packet as $crate::mc_types::Packet>::get(data)?));
+                    }
+                )*
+                $(
+                    return Ok(Self::$unknown {
+                        id: packet_id,
+                        data: $crate::mc_types::get_remaining(data).to_vec(),
+                    });
+                )?
+                Err($crate::mc_types::PacketError::InvalidPacketId(packet_id)
+                    .into())
+            }
+
+            pub fn packet_id(&self) -> i32 {
+                match self {
+                    $(Self::$variant(_) =>
+                        <$packet as $crate::mc_types::Packet>::PACKET_ID,)*
+                    $(Self::$unknown { id, .. } => *id,)?
+                }
+            }
+
+            pub fn convert(&self) -> $crate::mc_types::Result<Vec<u8>> {
+                match self {
+                    $(Self::$variant(packet) =>
+                        $crate::mc_types::Packet::convert(packet),)*
+                    $(Self::$unknown { id, data } => {
+                        let mut out = $crate::mc_types::convert_var_int(*id);
+                        out.extend_from_slice(data);
+                        Ok(out)
+                    },)?
+                }
+            }
+
+            pub async fn write<T: $crate::mc_types::ProtocolWrite + Send>(
+                &self,
+                conn: &mut T,
+            ) -> $crate::mc_types::Result<()> {
+                conn.write_data(&mut self.convert()?).await
+            }
+
+            pub async fn read_versioned<T: $crate::mc_types::ProtocolRead>(
+                conn: &mut T,
+                version: $crate::version::ProtocolVersion,
+            ) -> $crate::mc_types::Result<Self> {
+                let data = conn.read_data().await?;
+                let mut data = data.as_slice();
+                let packet_id = $crate::mc_types::get_var_int(&mut data)?;
+                Self::get_versioned(packet_id, &mut data, version)
+                    .map_err(|error| error
+                        .with_packet_id(packet_id)
+                        .with_state(Self::STATE, Self::DIRECTION))
+            }
+
+            #[allow(unreachable_code)]
+            pub fn get_versioned(
+                packet_id: i32,
+                data: &mut &[u8],
+                version: $crate::version::ProtocolVersion,
+            ) -> $crate::mc_types::Result<Self> {
+                $(
+                    if Some(packet_id) ==
+                        <$packet as $crate::mc_types::Packet>::packet_id_for(
+                            version)
+                    {
+                        return Ok(Self::$variant(
+                            <$packet as $crate::mc_types::Packet>
+                                ::get_versioned(data, version)?));
+                    }
+                )*
+                $(
+                    return Ok(Self::$unknown {
+                        id: packet_id,
+                        data: $crate::mc_types::get_remaining(data).to_vec(),
+                    });
+                )?
+                Err($crate::mc_types::PacketError::InvalidPacketId(packet_id)
+                    .into())
+            }
+
+            /// The ids of the packets available in `version`, which must
+            /// be unique for reads to be unambiguous.
+            pub fn packet_ids_for(
+                version: $crate::version::ProtocolVersion,
+            ) -> Vec<i32> {
+                [$(<$packet as $crate::mc_types::Packet>::packet_id_for(
+                    version),)*]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+
+            pub fn packet_id_for(
+                &self,
+                version: $crate::version::ProtocolVersion,
+            ) -> Option<i32> {
+                match self {
+                    $(Self::$variant(_) =>
+                        <$packet as $crate::mc_types::Packet>::packet_id_for(
+                            version),)*
+                    $(Self::$unknown { id, .. } => Some(*id),)?
+                }
+            }
+
+            pub fn convert_versioned(
+                &self,
+                version: $crate::version::ProtocolVersion,
+            ) -> $crate::mc_types::Result<Vec<u8>> {
+                match self {
+                    $(Self::$variant(packet) =>
+                        $crate::mc_types::Packet::convert_versioned(
+                            packet, version),)*
+                    $(Self::$unknown { .. } => self.convert(),)?
+                }
+            }
+
+            pub async fn write_versioned<
+                T: $crate::mc_types::ProtocolWrite + Send,
+            >(
+                &self,
+                conn: &mut T,
+                version: $crate::version::ProtocolVersion,
+            ) -> $crate::mc_types::Result<()> {
+                conn.write_data(&mut self.convert_versioned(version)?).await
+            }
+        }
+
+        $(
+            impl From<$packet> for $name {
+                fn from(packet: $packet) -> Self {
+                    Self::$variant(packet)
+                }
+            }
+        )*
+    };
+}
+
+fn compression_threshold(threshold: i32) -> Option<usize> {
+    if threshold < 0 {
+        None
+    } else {
+        Some(threshold as usize)
+    }
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    aes_cipher: &mut Option<McCipher>,
+    compression_threshold: Option<usize>,
+    max_packet_length: usize,
+) -> Result<Vec<u8>> {
+    let data = match aes_cipher {
+        Some(aes_cipher) => {
+            let length = read_var_int_stream_encrypted(
+                stream, aes_cipher).await?;
+            let length = check_packet_length(length, max_packet_length)?;
+
+            let mut buffer: Vec<u8> = vec![0; length];
+            stream.read_exact(&mut buffer).await?;
+            aes_cipher.decrypt_aes(buffer)
+        },
+        None => {
+            let length = read_var_int_stream(stream).await?;
+            let length = check_packet_length(length, max_packet_length)?;
+
+            let mut buffer: Vec<u8> = vec![0; length];
+            stream.read_exact(&mut buffer).await?;
+            buffer
+        }
+    };
+    match compression_threshold {
+        Some(threshold) => decompress_frame(data, threshold),
+        None => Ok(data),
+    }
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(
+    stream: &mut W,
+    aes_cipher: &mut Option<McCipher>,
+    compression_threshold: Option<usize>,
+    data: &mut Vec<u8>,
+) -> Result<()> {
+    let mut data = match compression_threshold {
+        Some(threshold) => compress_frame(data, threshold)?,
+        None => std::mem::take(data),
+    };
+    let mut out_data = convert_var_int(data.len() as i32);
+    out_data.append(&mut data);
+    match aes_cipher {
+        Some(aes_cipher) => {
+            stream.write_all(&aes_cipher.encrypt_aes(out_data)).await?;
+
+            Ok(())
+        },
+        None => {
+            stream.write_all(&out_data).await?;
+
+            Ok(())
         }
     }
+}
 
-    let varint = get_var_int(&mut data)?;
+fn check_packet_length(length: i32, max_packet_length: usize) -> Result<usize> {
+    if length < 0 {
+        return Err(ProtocolError::Framing(PacketError::ValueTooLarge));
+    }
+    if length as usize > max_packet_length {
+        return Err(ProtocolError::Framing(PacketError::LengthExceeded {
+            field: "Packet",
+            length: length as usize,
+            max: max_packet_length,
+        }));
+    }
+    Ok(length as usize)
+}
+
+fn compress_frame(data: &mut Vec<u8>, threshold: usize) -> Result<Vec<u8>> {
+    if data.len() < threshold {
+        let mut out_data = convert_var_int(0);
+        out_data.append(data);
+        return Ok(out_data);
+    }
+    let mut encoder = ZlibEncoder::new(
+        convert_var_int(data.len() as i32), Compression::default());
+    encoder.write_all(data)
+        .map_err(|error| ProtocolError::Compression(error.to_string()))?;
+    data.clear();
+    encoder.finish()
+        .map_err(|error| ProtocolError::Compression(error.to_string()))
+}
+
+fn decompress_frame(data: Vec<u8>, threshold: usize) -> Result<Vec<u8>> {
+    let mut cursor = data.as_slice();
+    let data_length = get_var_int(&mut cursor)
+        .map_err(|_| ProtocolError::Compression(
+            "invalid data length".to_string()))?;
+    if data_length == 0 {
+        return Ok(cursor.to_vec());
+    }
+    if data_length < 0 || (data_length as usize) < threshold {
+        return Err(ProtocolError::Compression(format!(
+            "data length {} is below threshold {}", data_length, threshold)));
+    }
+    if (data_length as usize) > MAX_UNCOMPRESSED_LENGTH {
+        return Err(ProtocolError::Compression(format!(
+            "data length {} exceeds maximum of {}",
+            data_length, MAX_UNCOMPRESSED_LENGTH)));
+    }
+    let data_length = data_length as usize;
+
+    let mut out_data: Vec<u8> = Vec::with_capacity(data_length);
+    ZlibDecoder::new(cursor)
+        .take(data_length as u64 + 1)
+        .read_to_end(&mut out_data)
+        .map_err(|error| ProtocolError::Compression(error.to_string()))?;
+    if out_data.len() != data_length {
+        return Err(ProtocolError::Compression(format!(
+            "inflated length {} does not match data length {}",
+            out_data.len(), data_length)));
+    }
+    Ok(out_data)
+}
+
+async fn read_var_int_stream<R: AsyncRead + Unpin>(
+    stream: &mut R,
+) -> Result<i32> {
+    let first_byte = stream.read_u8().await?;
+    continue_var_int_stream(stream, first_byte).await
+}
+async fn continue_var_int_stream<R: AsyncRead + Unpin>(
+    stream: &mut R,
+    first_byte: u8,
+) -> Result<i32> {
+    let mut data: Vec<u8> = vec![first_byte];
+
+    while (data[data.len() - 1] & CONTINUE_BIT) != 0 {
+        if data.len() >= MAX_PACKET_LENGTH_BYTES {
+            return Err(ProtocolError::Framing(PacketError::ValueTooLarge));
+        }
+        data.push(stream.read_u8().await?);
+    }
+
+    let varint = get_var_int(&mut data.as_slice())
+        .map_err(|_| ProtocolError::Framing(PacketError::ValueTooLarge))?;
 
     Ok(varint)
 }
-async fn read_var_int_stream_encrypted(
-    stream: &mut OwnedReadHalf,
+async fn read_var_int_stream_encrypted<R: AsyncRead + Unpin>(
+    stream: &mut R,
     cipher: &mut McCipher,
 ) -> Result<i32> {
     let mut data: Vec<u8> = vec![];
@@ -425,60 +1127,88 @@ async fn read_var_int_stream_encrypted(
         if (current_byte & CONTINUE_BIT) == 0 {
             break;
         }
+        if data.len() >= MAX_PACKET_LENGTH_BYTES {
+            return Err(ProtocolError::Framing(PacketError::ValueTooLarge));
+        }
     }
 
-    let varint = get_var_int(&mut data)?;
+    let varint = get_var_int(&mut data.as_slice())
+        .map_err(|_| ProtocolError::Framing(PacketError::ValueTooLarge))?;
 
     Ok(varint)
 }
 
 pub trait PacketArray: Sized {
-    fn get(data: &mut Vec<u8>) -> Result<Self>;
-    fn convert(&self) -> Vec<u8>;
+    fn get(data: &mut &[u8]) -> Result<Self>;
+    fn try_convert(&self) -> Result<Vec<u8>>;
+
+    fn get_array(data: &mut &[u8]) -> Result<Vec<Self>> {
+        Self::get_array_limited(data, data.len())
+    }
 
-    fn get_array(data: &mut Vec<u8>) -> Result<Vec<Self>> {
+    fn get_array_limited(data: &mut &[u8], max: usize) -> Result<Vec<Self>> {
         let length = get_var_int(data)?;
-        let mut out_data: Vec<Self> = vec![];
+        if length < 0 {
+            return Err(PacketError::ValueTooLarge.into());
+        }
+        let length = length as usize;
+        if length > max || length > data.len() {
+            return Err(ProtocolError::from(PacketError::LengthExceeded {
+                field: "Array",
+                length,
+                max: max.min(data.len()),
+            }));
+        }
+        let mut out_data: Vec<Self> = Vec::with_capacity(length);
         for _ in 0..length {
             out_data.push(Self::get(data)?);
         }
         Ok(out_data)
     }
 
-    fn convert_array(array: &mut Vec<Self>) -> Vec<u8> {
+    fn try_convert_array(array: &[Self]) -> Result<Vec<u8>> {
         let length = array.len() as i32;
         let mut data: Vec<u8> = convert_var_int(length);
         for element in array {
-            data.append(&mut Self::convert(element));
+            data.append(&mut Self::try_convert(element)?);
         }
-        data
+        Ok(data)
     }
 }
 
-pub fn get_bool(data: &mut Vec<u8>) -> bool {
-    data.remove(0) != 0
+impl PacketArray for String {
+    fn get(data: &mut &[u8]) -> Result<Self> {
+        get_string(data)
+    }
+
+    fn try_convert(&self) -> Result<Vec<u8>> {
+        Ok(convert_string(self))
+    }
+}
+
+pub fn get_bool(data: &mut &[u8]) -> Result<bool> {
+    Ok(u8::from_be_bytes(get_bytes_fixed(data, "Boolean")?) != 0)
 }
 pub fn convert_bool(value: bool) -> Vec<u8> {
     vec![value as u8]
 }
 
-pub fn get_u8(data: &mut Vec<u8>) -> u8 {
-    data.remove(0)
+pub fn get_u8(data: &mut &[u8]) -> Result<u8> {
+    Ok(u8::from_be_bytes(get_bytes_fixed(data, "Unsigned Byte")?))
 }
 pub fn convert_u8(value: u8) -> Vec<u8> {
     vec![value]
 }
 
-pub fn get_i8(data: &mut Vec<u8>) -> i8 {
-    get_u8(data) as i8
+pub fn get_i8(data: &mut &[u8]) -> Result<i8> {
+    Ok(get_u8(data)? as i8)
 }
 pub fn convert_i8(value: i8) -> Vec<u8> {
     convert_u8(value as u8)
 }
 
-pub fn get_u16(data: &mut Vec<u8>) -> u16 {
-    ((data.remove(0) as u16) << 8) |
-    (data.remove(0) as u16)
+pub fn get_u16(data: &mut &[u8]) -> Result<u16> {
+    Ok(u16::from_be_bytes(get_bytes_fixed(data, "Unsigned Short")?))
 }
 pub fn convert_u16(value: u16) -> Vec<u8> {
     vec![
@@ -487,51 +1217,41 @@ pub fn convert_u16(value: u16) -> Vec<u8> {
     ]
 }
 
-pub fn get_i16(data: &mut Vec<u8>) -> i16 {
-    get_u16(data) as i16
+pub fn get_i16(data: &mut &[u8]) -> Result<i16> {
+    Ok(get_u16(data)? as i16)
 }
 pub fn convert_i16(value: i16) -> Vec<u8> {
     convert_u16(value as u16)
 }
 
-pub fn get_u32(data: &mut Vec<u8>) -> u32 {
-    ((data.remove(0) as u32) << 24) |
-    ((data.remove(0) as u32) << 16) |
-    ((data.remove(0) as u32) << 8) |
-    (data.remove(0) as u32)
+pub fn get_u32(data: &mut &[u8]) -> Result<u32> {
+    Ok(u32::from_be_bytes(get_bytes_fixed(data, "Unsigned Int")?))
 }
 pub fn convert_u32(value: u32) -> Vec<u8> {
     vec![
-        ((value & 0xFF0000) >> 24) as u8,
+        ((value & 0xFF000000) >> 24) as u8,
         ((value & 0xFF0000) >> 16) as u8,
         ((value & 0xFF00) >> 8) as u8,
         (value & 0xFF) as u8,
     ]
 }
 
-pub fn get_i32(data: &mut Vec<u8>) -> i32 {
-    get_u32(data) as i32
+pub fn get_i32(data: &mut &[u8]) -> Result<i32> {
+    Ok(get_u32(data)? as i32)
 }
 pub fn convert_i32(value: i32) -> Vec<u8> {
     convert_u32(value as u32)
 }
 
-pub fn get_f32(data: &mut Vec<u8>) -> f32 {
-    get_u32(data) as f32
+pub fn get_f32(data: &mut &[u8]) -> Result<f32> {
+    Ok(f32::from_bits(get_u32(data)?))
 }
 pub fn convert_f32(value: f32) -> Vec<u8> {
-    convert_u32(value as u32)
+    convert_u32(value.to_bits())
 }
 
-pub fn get_u64(data: &mut Vec<u8>) -> u64 {
-    ((data.remove(0) as u64) << 56) |
-    ((data.remove(0) as u64) << 48) |
-    ((data.remove(0) as u64) << 40) |
-    ((data.remove(0) as u64) << 32) |
-    ((data.remove(0) as u64) << 24) |
-    ((data.remove(0) as u64) << 16) |
-    ((data.remove(0) as u64) << 8) |
-    (data.remove(0) as u64)
+pub fn get_u64(data: &mut &[u8]) -> Result<u64> {
+    Ok(u64::from_be_bytes(get_bytes_fixed(data, "Unsigned Long")?))
 }
 pub fn convert_u64(value: u64) -> Vec<u8> {
     vec![
@@ -546,37 +1266,22 @@ pub fn convert_u64(value: u64) -> Vec<u8> {
     ]
 }
 
-pub fn get_i64(data: &mut Vec<u8>) -> i64 {
-    get_u64(data) as i64
+pub fn get_i64(data: &mut &[u8]) -> Result<i64> {
+    Ok(get_u64(data)? as i64)
 }
 pub fn convert_i64(value: i64) -> Vec<u8> {
     convert_u64(value as u64)
 }
 
-pub fn get_f64(data: &mut Vec<u8>) -> f64 {
-    get_u64(data) as f64
+pub fn get_f64(data: &mut &[u8]) -> Result<f64> {
+    Ok(f64::from_bits(get_u64(data)?))
 }
 pub fn convert_f64(value: f64) -> Vec<u8> {
-    convert_u64(value as u64)
+    convert_u64(value.to_bits())
 }
 
-pub fn get_uuid(data: &mut Vec<u8>) -> u128 {
-    ((data.remove(0) as u128) << 120) |
-    ((data.remove(0) as u128) << 112) |
-    ((data.remove(0) as u128) << 104) |
-    ((data.remove(0) as u128) << 96) |
-    ((data.remove(0) as u128) << 88) |
-    ((data.remove(0) as u128) << 80) |
-    ((data.remove(0) as u128) << 72) |
-    ((data.remove(0) as u128) << 64) |
-    ((data.remove(0) as u128) << 56) |
-    ((data.remove(0) as u128) << 48) |
-    ((data.remove(0) as u128) << 40) |
-    ((data.remove(0) as u128) << 32) |
-    ((data.remove(0) as u128) << 24) |
-    ((data.remove(0) as u128) << 16) |
-    ((data.remove(0) as u128) << 8) |
-    (data.remove(0) as u128)
+pub fn get_uuid(data: &mut &[u8]) -> Result<u128> {
+    Ok(u128::from_be_bytes(get_bytes_fixed(data, "UUID")?))
 }
 pub fn convert_uuid(value: u128) -> Vec<u8> {
     vec![
@@ -599,62 +1304,82 @@ pub fn convert_uuid(value: u128) -> Vec<u8> {
     ]
 }
 pub fn uuid_u128_to_string(uuid: u128) -> String {
-    let uuid_bytes = convert_uuid(uuid);
     format!(
         "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
-        get_u32(&mut vec![
-            uuid_bytes[0],
-            uuid_bytes[1],
-            uuid_bytes[2],
-            uuid_bytes[3],
-        ]),
-        get_u16(&mut vec![uuid_bytes[4], uuid_bytes[5]]),
-        get_u16(&mut vec![uuid_bytes[6], uuid_bytes[7]]),
-        get_u16(&mut vec![uuid_bytes[8], uuid_bytes[9]]),
-        get_u64(&mut vec![
-            0,
-            0,
-            uuid_bytes[10],
-            uuid_bytes[11],
-            uuid_bytes[12],
-            uuid_bytes[13],
-            uuid_bytes[14],
-            uuid_bytes[15],
-        ]),
+        uuid >> 96,
+        (uuid >> 80) & 0xFFFF,
+        (uuid >> 64) & 0xFFFF,
+        (uuid >> 48) & 0xFFFF,
+        uuid & 0xFFFFFFFFFFFF,
     )
 }
 pub fn uuid_string_to_u128(uuid: &str) -> Result<u128> {
     let cleaned_uuid = uuid.replace("-", "");
     if cleaned_uuid.len() != 32 {
-        return Err(Box::new(PacketError::InvalidUUIDString));
+        return Err(PacketError::InvalidUUIDString.into());
     }
-    Ok(u128::from_str_radix(&cleaned_uuid, 16)?)
+    u128::from_str_radix(&cleaned_uuid, 16)
+        .map_err(|_| PacketError::InvalidUUIDString.into())
 }
 
-pub fn get_var_int(data: &mut Vec<u8>) -> Result<i32> {
-    Ok(get_var(data, 32)? as i32)
+pub fn get_var_int(data: &mut &[u8]) -> Result<i32> {
+    Ok(get_var(data, 32, "VarInt")? as i32)
 }
 pub fn convert_var_int(value: i32) -> Vec<u8> {
     convert_var(value as i64)
 }
 
-pub fn get_var_long(data: &mut Vec<u8>) -> Result<i64> {
-    get_var(data, 64)
+pub fn get_var_int_array(data: &mut &[u8]) -> Result<Vec<i32>> {
+    get_var_int_array_limited(data, data.len())
+}
+pub fn get_var_int_array_limited(
+    data: &mut &[u8],
+    max: usize,
+) -> Result<Vec<i32>> {
+    let length = get_var_int(data)?;
+    if length < 0 {
+        return Err(PacketError::ValueTooLarge.into());
+    }
+    let length = length as usize;
+    if length > max || length > data.len() {
+        return Err(ProtocolError::from(PacketError::LengthExceeded {
+            field: "Array",
+            length,
+            max: max.min(data.len()),
+        }));
+    }
+    let mut out_data: Vec<i32> = Vec::with_capacity(length);
+    for _ in 0..length {
+        out_data.push(get_var_int(data)?);
+    }
+    Ok(out_data)
+}
+pub fn convert_var_int_array(array: &[i32]) -> Vec<u8> {
+    let mut data = convert_var_int(array.len() as i32);
+    for value in array {
+        data.append(&mut convert_var_int(*value));
+    }
+    data
+}
+
+pub fn get_var_long(data: &mut &[u8]) -> Result<i64> {
+    get_var(data, 64, "VarLong")
 }
 pub fn convert_var_long(value: i64) -> Vec<u8> {
     convert_var(value)
 }
 
-fn get_var(data: &mut Vec<u8>, size: u8) -> Result<i64> {
+fn get_var(data: &mut &[u8], size: u8, field: &'static str) -> Result<i64> {
     let mut value: i64 = 0;
     let mut position: u8 = 0;
 
     loop {
         if data.is_empty() {
-            return Err(Box::new(PacketError::RanOutOfBytes));
+            return Err(PacketError::RanOutOfBytes(field).into());
         }
 
-        let current_byte = data.remove(0);
+        let current_byte = data[0];
+        *data = &data[1..];
         value |= ((current_byte & SEGMENT_BITS) as i64) << position;
 
         if (current_byte & CONTINUE_BIT) == 0 {
@@ -664,7 +1389,7 @@ fn get_var(data: &mut Vec<u8>, size: u8) -> Result<i64> {
         position += 7;
 
         if position >= size {
-            return Err(Box::new(PacketError::ValueTooLarge));
+            return Err(PacketError::ValueTooLarge.into());
         }
     }
 
@@ -683,11 +1408,38 @@ fn convert_var(mut value: i64) -> Vec<u8> {
     }
 }
 
-pub fn get_string(data: &mut Vec<u8>) -> Result<String> {
-    let length = get_var_int(data)? as usize;
-    let buffer = data[..length].to_vec();
-    for _ in 0..length { data.remove(0); }
-    Ok(String::from_utf8_lossy(&buffer).to_string())
+pub fn get_str<'a>(data: &mut &'a [u8]) -> Result<Cow<'a, str>> {
+    get_str_limited(data, MAX_STRING_LENGTH)
+}
+pub fn get_str_limited<'a>(
+    data: &mut &'a [u8],
+    max: usize,
+) -> Result<Cow<'a, str>> {
+    let length = get_var_int(data)?;
+    if length > 0 && length as usize > max * 3 {
+        return Err(ProtocolError::from(PacketError::LengthExceeded {
+            field: "String",
+            length: length as usize,
+            max: max * 3,
+        }));
+    }
+    let buffer = get_slice(data, length, "String")?;
+    let value = String::from_utf8_lossy(buffer);
+    let chars = value.encode_utf16().count();
+    if chars > max {
+        return Err(ProtocolError::from(PacketError::LengthExceeded {
+            field: "String",
+            length: chars,
+            max,
+        }));
+    }
+    Ok(value)
+}
+pub fn get_string(data: &mut &[u8]) -> Result<String> {
+    Ok(get_str(data)?.into_owned())
+}
+pub fn get_string_limited(data: &mut &[u8], max: usize) -> Result<String> {
+    Ok(get_str_limited(data, max)?.into_owned())
 }
 pub fn convert_string(s: &str) -> Vec<u8> {
     let length = s.len() as i32;
@@ -696,15 +1448,111 @@ pub fn convert_string(s: &str) -> Vec<u8> {
     data
 }
 
-pub fn get_byte_array(data: &mut Vec<u8>) -> Result<Vec<u8>> {
-    let length = get_var_int(data)? as usize;
-    let buffer = data[..length].to_vec();
-    for _ in 0..length { data.remove(0); }
-    Ok(buffer)
+pub fn get_bytes<'a>(data: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let length = get_var_int(data)?;
+    get_slice(data, length, "Byte Array")
 }
-pub fn convert_byte_array(mut s: &mut Vec<u8>) -> Vec<u8> {
+pub fn get_byte_array(data: &mut &[u8]) -> Result<Vec<u8>> {
+    Ok(get_bytes(data)?.to_vec())
+}
+pub fn get_byte_array_limited(
+    data: &mut &[u8],
+    max: usize,
+) -> Result<Vec<u8>> {
+    let mut cursor = *data;
+    let length = get_var_int(&mut cursor)?;
+    if length > 0 && length as usize > max {
+        return Err(ProtocolError::from(PacketError::LengthExceeded {
+            field: "Byte Array",
+            length: length as usize,
+            max,
+        }));
+    }
+    get_byte_array(data)
+}
+pub fn convert_byte_array(s: &[u8]) -> Vec<u8> {
     let length = s.len() as i32;
     let mut data = convert_var_int(length);
-    data.append(&mut s);
+    data.extend_from_slice(s);
     data
 }
+
+pub fn get_byte_array_fixed<const N: usize>(
+    data: &mut &[u8],
+) -> Result<[u8; N]> {
+    get_bytes_fixed(data, "Fixed Byte Array")
+}
+
+pub fn get_nbt(data: &mut &[u8]) -> Result<Tag> {
+    <Tag as PacketArray>::get(data)
+}
+pub fn get_optional_nbt(data: &mut &[u8]) -> Result<Option<Tag>> {
+    nbt::get_network(data)
+}
+pub fn convert_nbt(tag: &Tag) -> Result<Vec<u8>> {
+    nbt::convert_network(tag)
+}
+pub fn convert_optional_nbt(tag: Option<&Tag>) -> Result<Vec<u8>> {
+    nbt::convert_network_optional(tag)
+}
+
+pub fn get_component(
+    data: &mut &[u8],
+    version: ProtocolVersion,
+) -> Result<TextComponent> {
+    if version >= ProtocolVersion::V1_20_3 {
+        TextComponent::from_nbt(&get_nbt(data)?)
+    } else {
+        get_json_component(data)
+    }
+}
+pub fn convert_component(
+    component: &TextComponent,
+    version: ProtocolVersion,
+) -> Result<Vec<u8>> {
+    if version >= ProtocolVersion::V1_20_3 {
+        convert_nbt(&component.to_nbt()?)
+    } else {
+        convert_json_component(component)
+    }
+}
+
+pub fn get_json_component(data: &mut &[u8]) -> Result<TextComponent> {
+    get_json_component_limited(data, MAX_COMPONENT_LENGTH)
+}
+pub fn get_json_component_limited(
+    data: &mut &[u8],
+    max: usize,
+) -> Result<TextComponent> {
+    Ok(serde_json::from_str(&get_str_limited(data, max)?)?)
+}
+pub fn convert_json_component(component: &TextComponent) -> Result<Vec<u8>> {
+    Ok(convert_string(&serde_json::to_string(component)?))
+}
+
+pub fn get_remaining<'a>(data: &mut &'a [u8]) -> &'a [u8] {
+    let buffer = *data;
+    *data = &buffer[buffer.len()..];
+    buffer
+}
+
+fn get_bytes_fixed<const N: usize>(
+    data: &mut &[u8],
+    field: &'static str,
+) -> Result<[u8; N]> {
+    let buffer = get_slice(data, N as i32, field)?;
+    Ok(buffer.try_into().unwrap())
+}
+
+fn get_slice<'a>(
+    data: &mut &'a [u8],
+    length: i32,
+    field: &'static str,
+) -> Result<&'a [u8]> {
+    if length < 0 || length as usize > data.len() {
+        return Err(PacketError::RanOutOfBytes(field).into());
+    }
+    let (buffer, rest) = data.split_at(length as usize);
+    *data = rest;
+    Ok(buffer)
+}
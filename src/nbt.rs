@@ -0,0 +1,584 @@
+// Yeahbut October 2026
+
+use crate::mc_types::{self, PacketArray, PacketError, Result};
+
+pub const MAX_DEPTH: usize = 512;
+pub const MAX_SIZE: usize = 2097152;
+pub const MAX_STRING_LENGTH: usize = 65535;
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Compound {
+    entries: Vec<(String, Tag)>,
+}
+
+impl Tag {
+    pub fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => TAG_BYTE,
+            Tag::Short(_) => TAG_SHORT,
+            Tag::Int(_) => TAG_INT,
+            Tag::Long(_) => TAG_LONG,
+            Tag::Float(_) => TAG_FLOAT,
+            Tag::Double(_) => TAG_DOUBLE,
+            Tag::ByteArray(_) => TAG_BYTE_ARRAY,
+            Tag::String(_) => TAG_STRING,
+            Tag::List(_) => TAG_LIST,
+            Tag::Compound(_) => TAG_COMPOUND,
+            Tag::IntArray(_) => TAG_INT_ARRAY,
+            Tag::LongArray(_) => TAG_LONG_ARRAY,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Tag::Compound(compound) => Some(compound),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self {
+            Tag::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Tag::Byte(value) => Some(value as i64),
+            Tag::Short(value) => Some(value as i64),
+            Tag::Int(value) => Some(value as i64),
+            Tag::Long(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Tag::Float(value) => Some(value as f64),
+            Tag::Double(value) => Some(value),
+            _ => self.as_i64().map(|value| value as f64),
+        }
+    }
+}
+
+impl Compound {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        self.entries.iter()
+            .rfind(|(key, _)| key == name)
+            .map(|(_, tag)| tag)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+        self.entries.iter_mut()
+            .rfind(|(key, _)| key == name)
+            .map(|(_, tag)| tag)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        tag: impl Into<Tag>,
+    ) -> Option<Tag> {
+        let name = name.into();
+        let tag = tag.into();
+        match self.get_mut(&name) {
+            Some(existing) => Some(std::mem::replace(existing, tag)),
+            None => {
+                self.entries.push((name, tag));
+                None
+            },
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Tag> {
+        let index = self.entries.iter().rposition(|(key, _)| key == name)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Tag)> {
+        self.entries.iter().map(|(key, tag)| (key.as_str(), tag))
+    }
+}
+
+impl FromIterator<(String, Tag)> for Compound {
+    fn from_iter<I: IntoIterator<Item = (String, Tag)>>(iter: I) -> Self {
+        let mut compound = Compound::new();
+        for (name, tag) in iter {
+            compound.insert(name, tag);
+        }
+        compound
+    }
+}
+
+impl IntoIterator for Compound {
+    type Item = (String, Tag);
+    type IntoIter = std::vec::IntoIter<(String, Tag)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl From<bool> for Tag {
+    fn from(value: bool) -> Self {
+        Tag::Byte(value as i8)
+    }
+}
+
+impl From<i8> for Tag {
+    fn from(value: i8) -> Self {
+        Tag::Byte(value)
+    }
+}
+
+impl From<i16> for Tag {
+    fn from(value: i16) -> Self {
+        Tag::Short(value)
+    }
+}
+
+impl From<i32> for Tag {
+    fn from(value: i32) -> Self {
+        Tag::Int(value)
+    }
+}
+
+impl From<i64> for Tag {
+    fn from(value: i64) -> Self {
+        Tag::Long(value)
+    }
+}
+
+impl From<f32> for Tag {
+    fn from(value: f32) -> Self {
+        Tag::Float(value)
+    }
+}
+
+impl From<f64> for Tag {
+    fn from(value: f64) -> Self {
+        Tag::Double(value)
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(value: &str) -> Self {
+        Tag::String(value.to_string())
+    }
+}
+
+impl From<String> for Tag {
+    fn from(value: String) -> Self {
+        Tag::String(value)
+    }
+}
+
+impl From<Compound> for Tag {
+    fn from(value: Compound) -> Self {
+        Tag::Compound(value)
+    }
+}
+
+impl From<Vec<Tag>> for Tag {
+    fn from(value: Vec<Tag>) -> Self {
+        Tag::List(value)
+    }
+}
+
+impl PacketArray for Tag {
+    fn get(data: &mut &[u8]) -> Result<Self> {
+        get_network(data)?.ok_or_else(|| PacketError::InvalidNbt(
+            "unexpected end tag at root").into())
+    }
+
+    fn try_convert(&self) -> Result<Vec<u8>> {
+        convert_network(self)
+    }
+}
+
+impl PacketArray for Compound {
+    fn get(data: &mut &[u8]) -> Result<Self> {
+        match Tag::get(data)? {
+            Tag::Compound(compound) => Ok(compound),
+            _ => Err(PacketError::InvalidNbt(
+                "expected a compound at root").into()),
+        }
+    }
+
+    fn try_convert(&self) -> Result<Vec<u8>> {
+        let mut data = vec![TAG_COMPOUND];
+        write_compound(self, &mut data)?;
+        Ok(data)
+    }
+}
+
+pub fn get_network(data: &mut &[u8]) -> Result<Option<Tag>> {
+    get_network_limited(data, MAX_SIZE)
+}
+
+pub fn get_network_limited(
+    data: &mut &[u8],
+    max_size: usize,
+) -> Result<Option<Tag>> {
+    let mut reader = Reader { data, remaining: max_size };
+    let id = reader.get_u8()?;
+    if id == TAG_END {
+        return Ok(None);
+    }
+    reader.get_payload(id, 0).map(Some)
+}
+
+pub fn get_named(data: &mut &[u8]) -> Result<(String, Tag)> {
+    get_named_limited(data, MAX_SIZE)
+}
+
+pub fn get_named_limited(
+    data: &mut &[u8],
+    max_size: usize,
+) -> Result<(String, Tag)> {
+    let mut reader = Reader { data, remaining: max_size };
+    let id = reader.get_u8()?;
+    if id == TAG_END {
+        return Err(PacketError::InvalidNbt(
+            "unexpected end tag at root").into());
+    }
+    let name = reader.get_string()?;
+    let tag = reader.get_payload(id, 0)?;
+    Ok((name, tag))
+}
+
+pub fn convert_network(tag: &Tag) -> Result<Vec<u8>> {
+    let mut data = vec![tag.id()];
+    write_payload(tag, &mut data)?;
+    Ok(data)
+}
+
+pub fn convert_network_optional(tag: Option<&Tag>) -> Result<Vec<u8>> {
+    match tag {
+        Some(tag) => convert_network(tag),
+        None => Ok(vec![TAG_END]),
+    }
+}
+
+pub fn convert_named(name: &str, tag: &Tag) -> Result<Vec<u8>> {
+    let mut data = vec![tag.id()];
+    write_string(name, &mut data)?;
+    write_payload(tag, &mut data)?;
+    Ok(data)
+}
+
+pub fn decode_modified_utf8(bytes: &[u8]) -> Result<String> {
+    let invalid = || PacketError::InvalidNbt("invalid modified UTF-8 string");
+    let continuation = |index: usize| match bytes.get(index) {
+        Some(byte) if byte & 0xC0 == 0x80 => Ok((byte & 0x3F) as u16),
+        _ => Err(invalid()),
+    };
+    let mut units: Vec<u16> = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte & 0x80 == 0 {
+            units.push(byte as u16);
+            index += 1;
+        } else if byte & 0xE0 == 0xC0 {
+            units.push(((byte & 0x1F) as u16) << 6 | continuation(index + 1)?);
+            index += 2;
+        } else if byte & 0xF0 == 0xE0 {
+            units.push(((byte & 0x0F) as u16) << 12 |
+                continuation(index + 1)? << 6 |
+                continuation(index + 2)?);
+            index += 3;
+        } else {
+            return Err(invalid().into());
+        }
+    }
+    String::from_utf16(&units).map_err(|_| invalid().into())
+}
+
+pub fn encode_modified_utf8(value: &str) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => bytes.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            },
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            },
+        }
+    }
+    bytes
+}
+
+struct Reader<'a, 'b> {
+    data: &'a mut &'b [u8],
+    remaining: usize,
+}
+
+impl<'b> Reader<'_, 'b> {
+    fn charge(&mut self, size: usize) -> Result<()> {
+        if size > self.remaining {
+            return Err(PacketError::LengthExceeded {
+                field: "NBT",
+                length: size,
+                max: self.remaining,
+            }.into());
+        }
+        self.remaining -= size;
+        Ok(())
+    }
+
+    fn get_bytes(&mut self, length: usize) -> Result<&'b [u8]> {
+        self.charge(length)?;
+        if length > self.data.len() {
+            return Err(PacketError::RanOutOfBytes("NBT").into());
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        *self.data = rest;
+        Ok(bytes)
+    }
+
+    fn get_u8(&mut self) -> Result<u8> {
+        self.charge(1)?;
+        mc_types::get_u8(self.data)
+    }
+
+    fn get_length(&mut self, element_size: usize) -> Result<usize> {
+        self.charge(4)?;
+        let length = mc_types::get_i32(self.data)?;
+        if length < 0 {
+            return Err(PacketError::InvalidNbt("negative length").into());
+        }
+        let length = length as usize;
+        if length.saturating_mul(element_size) > self.data.len() {
+            return Err(PacketError::RanOutOfBytes("NBT").into());
+        }
+        Ok(length)
+    }
+
+    fn get_string(&mut self) -> Result<String> {
+        self.charge(2)?;
+        let length = mc_types::get_u16(self.data)? as usize;
+        decode_modified_utf8(self.get_bytes(length)?)
+    }
+
+    fn get_payload(&mut self, id: u8, depth: usize) -> Result<Tag> {
+        if depth >= MAX_DEPTH {
+            return Err(PacketError::InvalidNbt(
+                "maximum nesting depth exceeded").into());
+        }
+        match id {
+            TAG_LIST => self.get_list(depth),
+            TAG_COMPOUND => self.get_compound(depth),
+            _ => self.get_value(id),
+        }
+    }
+
+    fn get_list(&mut self, depth: usize) -> Result<Tag> {
+        let element_id = self.get_u8()?;
+        let length = self.get_length(0)?;
+        if element_id == TAG_END && length > 0 {
+            return Err(PacketError::InvalidNbt("list of end tags").into());
+        }
+        let mut list: Vec<Tag> =
+            Vec::with_capacity(length.min(self.data.len()));
+        for _ in 0..length {
+            list.push(self.get_payload(element_id, depth + 1)?);
+        }
+        Ok(Tag::List(list))
+    }
+
+    fn get_compound(&mut self, depth: usize) -> Result<Tag> {
+        let mut compound = Compound::new();
+        loop {
+            let id = self.get_u8()?;
+            if id == TAG_END {
+                break;
+            }
+            let name = self.get_string()?;
+            let tag = self.get_payload(id, depth + 1)?;
+            compound.entries.push((name, tag));
+        }
+        Ok(Tag::Compound(compound))
+    }
+
+    fn get_value(&mut self, id: u8) -> Result<Tag> {
+        Ok(match id {
+            TAG_BYTE => {
+                self.charge(1)?;
+                Tag::Byte(mc_types::get_i8(self.data)?)
+            },
+            TAG_SHORT => {
+                self.charge(2)?;
+                Tag::Short(mc_types::get_i16(self.data)?)
+            },
+            TAG_INT => {
+                self.charge(4)?;
+                Tag::Int(mc_types::get_i32(self.data)?)
+            },
+            TAG_LONG => {
+                self.charge(8)?;
+                Tag::Long(mc_types::get_i64(self.data)?)
+            },
+            TAG_FLOAT => {
+                self.charge(4)?;
+                Tag::Float(mc_types::get_f32(self.data)?)
+            },
+            TAG_DOUBLE => {
+                self.charge(8)?;
+                Tag::Double(mc_types::get_f64(self.data)?)
+            },
+            TAG_BYTE_ARRAY => {
+                let length = self.get_length(1)?;
+                Tag::ByteArray(self.get_bytes(length)?.iter()
+                    .map(|byte| *byte as i8)
+                    .collect())
+            },
+            TAG_STRING => Tag::String(self.get_string()?),
+            TAG_INT_ARRAY => {
+                let length = self.get_length(4)?;
+                self.charge(length * 4)?;
+                let mut array: Vec<i32> = Vec::with_capacity(length);
+                for _ in 0..length {
+                    array.push(mc_types::get_i32(self.data)?);
+                }
+                Tag::IntArray(array)
+            },
+            TAG_LONG_ARRAY => {
+                let length = self.get_length(8)?;
+                self.charge(length * 8)?;
+                let mut array: Vec<i64> = Vec::with_capacity(length);
+                for _ in 0..length {
+                    array.push(mc_types::get_i64(self.data)?);
+                }
+                Tag::LongArray(array)
+            },
+            _ => return Err(
+                PacketError::InvalidNbt("unknown tag type").into()),
+        })
+    }
+}
+
+fn write_string(value: &str, data: &mut Vec<u8>) -> Result<()> {
+    let mut bytes = encode_modified_utf8(value);
+    if bytes.len() > MAX_STRING_LENGTH {
+        return Err(PacketError::InvalidNbt(
+            "string longer than 65535 bytes").into());
+    }
+    data.append(&mut mc_types::convert_u16(bytes.len() as u16));
+    data.append(&mut bytes);
+    Ok(())
+}
+
+fn write_compound(compound: &Compound, data: &mut Vec<u8>) -> Result<()> {
+    for (name, tag) in compound.iter() {
+        data.push(tag.id());
+        write_string(name, data)?;
+        write_payload(tag, data)?;
+    }
+    data.push(TAG_END);
+    Ok(())
+}
+
+fn write_payload(tag: &Tag, data: &mut Vec<u8>) -> Result<()> {
+    match tag {
+        Tag::Byte(value) => data.append(&mut mc_types::convert_i8(*value)),
+        Tag::Short(value) => data.append(&mut mc_types::convert_i16(*value)),
+        Tag::Int(value) => data.append(&mut mc_types::convert_i32(*value)),
+        Tag::Long(value) => data.append(&mut mc_types::convert_i64(*value)),
+        Tag::Float(value) => data.append(&mut mc_types::convert_f32(*value)),
+        Tag::Double(value) => data.append(&mut mc_types::convert_f64(*value)),
+        Tag::ByteArray(array) => {
+            data.append(&mut mc_types::convert_i32(array.len() as i32));
+            data.extend(array.iter().map(|byte| *byte as u8));
+        },
+        Tag::String(value) => write_string(value, data)?,
+        Tag::List(list) => {
+            let element_id = list.first().map_or(TAG_END, Tag::id);
+            let mixed = list.iter().any(|tag| tag.id() != element_id);
+            data.push(if mixed { TAG_COMPOUND } else { element_id });
+            data.append(&mut mc_types::convert_i32(list.len() as i32));
+            for tag in list {
+                if mixed {
+                    let mut wrapper = Compound::new();
+                    wrapper.insert("", tag.clone());
+                    write_compound(&wrapper, data)?;
+                } else {
+                    write_payload(tag, data)?;
+                }
+            }
+        },
+        Tag::Compound(compound) => write_compound(compound, data)?,
+        Tag::IntArray(array) => {
+            data.append(&mut mc_types::convert_i32(array.len() as i32));
+            for value in array {
+                data.append(&mut mc_types::convert_i32(*value));
+            }
+        },
+        Tag::LongArray(array) => {
+            data.append(&mut mc_types::convert_i32(array.len() as i32));
+            for value in array {
+                data.append(&mut mc_types::convert_i64(*value));
+            }
+        },
+    }
+    Ok(())
+}
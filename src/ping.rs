@@ -0,0 +1,131 @@
+// Yeahbut October 2026
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::mc_types::{
+    self, ConnectionState, Direction, Packet, PacketError, ProtocolConnection,
+    ProtocolError, Result};
+use crate::handshake::serverbound::{Handshake, HandshakeIntent};
+use crate::status::{clientbound, serverbound};
+
+pub const DEFAULT_PORT: u16 = 25565;
+
+pub struct PingOptions {
+    pub protocol_version: i32,
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+}
+
+impl Default for PingOptions {
+    fn default() -> Self {
+        Self {
+            protocol_version: mc_types::VERSION_PROTOCOL,
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+pub struct PingResult {
+    pub status: clientbound::StatusResponseData,
+    pub raw_json: String,
+    pub latency: Duration,
+}
+
+pub async fn ping(address: &str) -> Result<PingResult> {
+    ping_with(address, &PingOptions::default()).await
+}
+
+pub async fn ping_with(
+    address: &str,
+    options: &PingOptions,
+) -> Result<PingResult> {
+    let (host, port) = split_address(address);
+    let stream = with_timeout(
+        options.connect_timeout, TcpStream::connect((host, port))).await??;
+    let mut conn = ProtocolConnection::from_stream(stream);
+    ping_connection(&mut conn, host, port, options).await
+}
+
+/// Runs the server list ping over an already open connection.
+pub async fn ping_connection<R, W>(
+    conn: &mut ProtocolConnection<R, W>,
+    host: &str,
+    port: u16,
+    options: &PingOptions,
+) -> Result<PingResult>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    Handshake {
+        protocol_version: options.protocol_version,
+        server_address: host.to_string(),
+        server_port: port,
+        next_state: HandshakeIntent::Status.next_state(),
+    }.write(conn).await?;
+    serverbound::Status {}.write(conn).await?;
+
+    let response = match with_timeout(
+        options.read_timeout,
+        clientbound::StatusPackets::read(conn),
+    ).await?? {
+        clientbound::StatusPackets::Status(status) => status,
+        packet => return Err(
+            PacketError::InvalidPacketId(packet.packet_id()).into()),
+    };
+    let status = response.get_json()?;
+
+    let payload = rand::random::<i64>();
+    let start = Instant::now();
+    serverbound::Ping { payload }.write(conn).await?;
+    let pong = match with_timeout(
+        options.read_timeout,
+        clientbound::StatusPackets::read(conn),
+    ).await?? {
+        clientbound::StatusPackets::Ping(pong) => pong,
+        packet => return Err(
+            PacketError::InvalidPacketId(packet.packet_id()).into()),
+    };
+    let latency = start.elapsed();
+    if pong.payload != payload {
+        return Err(ProtocolError::from(PacketError::PongMismatch {
+            expected: payload,
+            received: pong.payload,
+        })
+            .with_packet_id(clientbound::Ping::PACKET_ID)
+            .with_state(ConnectionState::Status, Direction::Clientbound));
+    }
+
+    Ok(PingResult {
+        status,
+        raw_json: response.response,
+        latency,
+    })
+}
+
+async fn with_timeout<F: std::future::Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output> {
+    timeout(duration, future).await.map_err(|_| ProtocolError::Io(
+        io::Error::new(io::ErrorKind::TimedOut, "server list ping timed out")))
+}
+
+fn split_address(address: &str) -> (&str, u16) {
+    if let Some((host, port)) = address.rsplit_once(':') {
+        // Bare IPv6 addresses contain colons but no port
+        if !host.contains(':') || host.ends_with(']') {
+            if let Ok(port) = port.parse() {
+                return (host.trim_start_matches('[').trim_end_matches(']'),
+                    port);
+            }
+        }
+    }
+    (address.trim_start_matches('[').trim_end_matches(']'), DEFAULT_PORT)
+}
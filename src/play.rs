@@ -1,39 +1,863 @@
 
-use crate::mc_types::{self, Packet, Result};
+pub mod clientbound {
 
+    use crate::mc_types::{
+        self, Packet, PacketArray, PacketError, ProtocolError, Result,
+        ResultContext};
+    use crate::nbt::{self, Compound};
+    use crate::text::TextComponent;
+    use crate::version::{ProtocolVersion, VERSION_COUNT};
 
-pub enum Play {
-    PlayPacket(PlayPacket),
-}
+    pub use crate::configuration::clientbound::{
+        RegistryTags, ReportDetail, Tag};
 
-impl Play {
-    pub async fn read<T: mc_types::ProtocolRead>(
-        conn: &mut T,
-    ) -> Result<Self> {
-        let mut data = conn.read_data().await?;
-        Ok(Self::PlayPacket(PlayPacket::get(&mut data)?))
+    crate::packet_enum! {
+        #[allow(clippy::large_enum_variant)]
+        pub enum Play (Play, Clientbound) {
+            BundleDelimiter(BundleDelimiter),
+            ChangeDifficulty(ChangeDifficulty),
+            ChunkBatchFinished(ChunkBatchFinished),
+            ChunkBatchStart(ChunkBatchStart),
+            ClearTitles(ClearTitles),
+            CloseContainer(CloseContainer),
+            CookieRequest(CookieRequest),
+            PluginMessage(PluginMessage),
+            Disconnect(Disconnect),
+            UnloadChunk(UnloadChunk),
+            GameEvent(GameEvent),
+            KeepAlive(KeepAlive),
+            Login(Login),
+            Ping(Ping),
+            PingResponse(PingResponse),
+            PlayerAbilities(PlayerAbilities),
+            SyncPlayerPosition(SyncPlayerPosition),
+            RemoveEntities(RemoveEntities),
+            SetActionBarText(SetActionBarText),
+            SetHeldItem(SetHeldItem),
+            SetCenterChunk(SetCenterChunk),
+            SetRenderDistance(SetRenderDistance),
+            SetDefaultSpawnPosition(SetDefaultSpawnPosition),
+            SetExperience(SetExperience),
+            SetHealth(SetHealth),
+            SetSimulationDistance(SetSimulationDistance),
+            SetSubtitleText(SetSubtitleText),
+            UpdateTime(UpdateTime),
+            SetTitleText(SetTitleText),
+            StartConfiguration(StartConfiguration),
+            StoreCookie(StoreCookie),
+            SystemChat(SystemChat),
+            SetTabListHeaderAndFooter(SetTabListHeaderAndFooter),
+            SetTickingState(SetTickingState),
+            StepTick(StepTick),
+            Transfer(Transfer),
+            UpdateTags(UpdateTags),
+            CustomReportDetails(CustomReportDetails),
+            ServerLinks(ServerLinks),
+            _ => Unknown,
+        }
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x00)]
+    pub struct BundleDelimiter {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x0B,
+        ids(V1_20_1 = 0x0C))]
+    pub struct ChangeDifficulty {
+        pub difficulty: u8,
+        pub locked: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0C, since = V1_20_2)]
+    pub struct ChunkBatchFinished {
+        #[packet(var_int)]
+        pub batch_size: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0D, since = V1_20_2)]
+    pub struct ChunkBatchStart {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x0F,
+        ids(V1_20_1 = 0x0E))]
+    pub struct ClearTitles {
+        pub reset: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x12,
+        ids(V1_20_1 = 0x11))]
+    pub struct CloseContainer {
+        pub window_id: u8,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x16, since = V1_20_5)]
+    pub struct CookieRequest {
+        pub key: String,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x19,
+        ids(V1_20_1 = 0x17, V1_20_2 = 0x18, V1_20_3 = 0x18))]
+    pub struct PluginMessage {
+        pub channel: String,
+        #[packet(remaining)]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x1D,
+        ids(V1_20_1 = 0x1A, V1_20_2 = 0x1B, V1_20_3 = 0x1B))]
+    pub struct Disconnect {
+        pub reason: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x21,
+        ids(V1_20_1 = 0x1E, V1_20_2 = 0x1F, V1_20_3 = 0x1F))]
+    pub struct UnloadChunk {
+        pub chunk_z: i32,
+        pub chunk_x: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x22,
+        ids(V1_20_1 = 0x1F, V1_20_2 = 0x20, V1_20_3 = 0x20))]
+    pub struct GameEvent {
+        pub event: u8,
+        pub value: f32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x26,
+        ids(V1_20_1 = 0x23, V1_20_2 = 0x24, V1_20_3 = 0x24))]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    pub struct Login {
+        pub entity_id: i32,
+        pub is_hardcore: bool,
+        pub dimension_names: Vec<String>,
+        // Sent here only before 1.20.2, and in configuration after
+        pub registry_codec: Compound,
+        pub max_players: i32,
+        pub view_distance: i32,
+        pub simulation_distance: i32,
+        pub reduced_debug_info: bool,
+        pub enable_respawn_screen: bool,
+        pub do_limited_crafting: bool,
+        pub dimension_type: i32,
+        pub dimension_type_name: String,
+        pub dimension_name: String,
+        pub hashed_seed: i64,
+        pub game_mode: u8,
+        pub previous_game_mode: i8,
+        pub is_debug: bool,
+        pub is_flat: bool,
+        pub death_location: Option<DeathLocation>,
+        pub portal_cooldown: i32,
+        pub enforces_secure_chat: bool,
+    }
+
+    // 1.20.2 moved the game modes and view settings, so the older layout
+    // cannot be expressed with version gated fields
+    impl Packet for Login {
+
+        const PACKET_ID: i32 = 0x2B;
+
+        fn get(data: &mut &[u8]) -> Result<Self> {
+            Self::get_versioned(data, ProtocolVersion::LATEST)
+        }
+
+        fn convert(&self) -> Result<Vec<u8>> {
+            self.convert_versioned(ProtocolVersion::LATEST)
+        }
+
+        const VERSIONED_IDS: [Option<i32>; VERSION_COUNT] =
+            mc_types::versioned_ids(Self::PACKET_ID, None, None, &[
+                (ProtocolVersion::V1_20_1, 0x28),
+                (ProtocolVersion::V1_20_2, 0x29),
+                (ProtocolVersion::V1_20_3, 0x29),
+            ]);
+
+        fn get_versioned(
+            data: &mut &[u8],
+            version: ProtocolVersion,
+        ) -> Result<Self> {
+            if version < ProtocolVersion::V1_20_2 {
+                return Self::get_v1_20_1(data);
+            }
+            let entity_id = mc_types::get_i32(data).field("entity_id")?;
+            let is_hardcore = mc_types::get_bool(data).field("is_hardcore")?;
+            let dimension_names = String::get_array(data)
+                .field("dimension_names")?;
+            let max_players = mc_types::get_var_int(data)
+                .field("max_players")?;
+            let view_distance = mc_types::get_var_int(data)
+                .field("view_distance")?;
+            let simulation_distance = mc_types::get_var_int(data)
+                .field("simulation_distance")?;
+            let reduced_debug_info = mc_types::get_bool(data)
+                .field("reduced_debug_info")?;
+            let enable_respawn_screen = mc_types::get_bool(data)
+                .field("enable_respawn_screen")?;
+            let do_limited_crafting = mc_types::get_bool(data)
+                .field("do_limited_crafting")?;
+            let (dimension_type, dimension_type_name) =
+                if version >= ProtocolVersion::V1_20_5 {
+                    (mc_types::get_var_int(data).field("dimension_type")?,
+                        String::new())
+                } else {
+                    (0, mc_types::get_string(data)
+                        .field("dimension_type_name")?)
+                };
+            let dimension_name = mc_types::get_string(data)
+                .field("dimension_name")?;
+            let hashed_seed = mc_types::get_i64(data).field("hashed_seed")?;
+            let game_mode = mc_types::get_u8(data).field("game_mode")?;
+            let previous_game_mode = mc_types::get_i8(data)
+                .field("previous_game_mode")?;
+            let is_debug = mc_types::get_bool(data).field("is_debug")?;
+            let is_flat = mc_types::get_bool(data).field("is_flat")?;
+            let death_location = get_death_location(data)?;
+            let portal_cooldown = mc_types::get_var_int(data)
+                .field("portal_cooldown")?;
+            let enforces_secure_chat = version >= ProtocolVersion::V1_20_5 &&
+                mc_types::get_bool(data).field("enforces_secure_chat")?;
+            Ok(Self {
+                entity_id,
+                is_hardcore,
+                dimension_names,
+                registry_codec: Compound::new(),
+                max_players,
+                view_distance,
+                simulation_distance,
+                reduced_debug_info,
+                enable_respawn_screen,
+                do_limited_crafting,
+                dimension_type,
+                dimension_type_name,
+                dimension_name,
+                hashed_seed,
+                game_mode,
+                previous_game_mode,
+                is_debug,
+                is_flat,
+                death_location,
+                portal_cooldown,
+                enforces_secure_chat,
+            })
+        }
+
+        fn convert_versioned(
+            &self,
+            version: ProtocolVersion,
+        ) -> Result<Vec<u8>> {
+            let mut data = mc_types::convert_var_int(
+                Self::packet_id_for(version).unwrap());
+            if version < ProtocolVersion::V1_20_2 {
+                self.convert_v1_20_1(&mut data)?;
+                return Ok(data);
+            }
+            data.append(&mut mc_types::convert_i32(self.entity_id));
+            data.append(&mut mc_types::convert_bool(self.is_hardcore));
+            data.append(&mut String::try_convert_array(
+                &self.dimension_names)?);
+            data.append(&mut mc_types::convert_var_int(self.max_players));
+            data.append(&mut mc_types::convert_var_int(self.view_distance));
+            data.append(&mut mc_types::convert_var_int(
+                self.simulation_distance));
+            data.append(&mut mc_types::convert_bool(self.reduced_debug_info));
+            data.append(&mut mc_types::convert_bool(
+                self.enable_respawn_screen));
+            data.append(&mut mc_types::convert_bool(
+                self.do_limited_crafting));
+            if version >= ProtocolVersion::V1_20_5 {
+                data.append(&mut mc_types::convert_var_int(
+                    self.dimension_type));
+            } else {
+                data.append(&mut mc_types::convert_string(
+                    &self.dimension_type_name));
+            }
+            data.append(&mut mc_types::convert_string(&self.dimension_name));
+            data.append(&mut mc_types::convert_i64(self.hashed_seed));
+            data.append(&mut mc_types::convert_u8(self.game_mode));
+            data.append(&mut mc_types::convert_i8(self.previous_game_mode));
+            data.append(&mut mc_types::convert_bool(self.is_debug));
+            data.append(&mut mc_types::convert_bool(self.is_flat));
+            self.convert_death_location(&mut data)?;
+            data.append(&mut mc_types::convert_var_int(self.portal_cooldown));
+            if version >= ProtocolVersion::V1_20_5 {
+                data.append(&mut mc_types::convert_bool(
+                    self.enforces_secure_chat));
+            }
+
+            Ok(data)
+        }
+
+    }
+
+    impl Login {
+        fn get_v1_20_1(data: &mut &[u8]) -> Result<Self> {
+            let entity_id = mc_types::get_i32(data).field("entity_id")?;
+            let is_hardcore = mc_types::get_bool(data).field("is_hardcore")?;
+            let game_mode = mc_types::get_u8(data).field("game_mode")?;
+            let previous_game_mode = mc_types::get_i8(data)
+                .field("previous_game_mode")?;
+            let dimension_names = String::get_array(data)
+                .field("dimension_names")?;
+            let registry_codec = match nbt::get_named(data)
+                .field("registry_codec")?
+            {
+                (_, nbt::Tag::Compound(compound)) => compound,
+                _ => return Err(ProtocolError::from(PacketError::InvalidNbt(
+                    "expected a compound at root"))
+                    .with_field("registry_codec")),
+            };
+            let dimension_type_name = mc_types::get_string(data)
+                .field("dimension_type_name")?;
+            let dimension_name = mc_types::get_string(data)
+                .field("dimension_name")?;
+            let hashed_seed = mc_types::get_i64(data).field("hashed_seed")?;
+            let max_players = mc_types::get_var_int(data)
+                .field("max_players")?;
+            let view_distance = mc_types::get_var_int(data)
+                .field("view_distance")?;
+            let simulation_distance = mc_types::get_var_int(data)
+                .field("simulation_distance")?;
+            let reduced_debug_info = mc_types::get_bool(data)
+                .field("reduced_debug_info")?;
+            let enable_respawn_screen = mc_types::get_bool(data)
+                .field("enable_respawn_screen")?;
+            let is_debug = mc_types::get_bool(data).field("is_debug")?;
+            let is_flat = mc_types::get_bool(data).field("is_flat")?;
+            let death_location = get_death_location(data)?;
+            let portal_cooldown = mc_types::get_var_int(data)
+                .field("portal_cooldown")?;
+            Ok(Self {
+                entity_id,
+                is_hardcore,
+                dimension_names,
+                registry_codec,
+                max_players,
+                view_distance,
+                simulation_distance,
+                reduced_debug_info,
+                enable_respawn_screen,
+                do_limited_crafting: false,
+                dimension_type: 0,
+                dimension_type_name,
+                dimension_name,
+                hashed_seed,
+                game_mode,
+                previous_game_mode,
+                is_debug,
+                is_flat,
+                death_location,
+                portal_cooldown,
+                enforces_secure_chat: false,
+            })
+        }
+
+        fn convert_v1_20_1(&self, data: &mut Vec<u8>) -> Result<()> {
+            data.append(&mut mc_types::convert_i32(self.entity_id));
+            data.append(&mut mc_types::convert_bool(self.is_hardcore));
+            data.append(&mut mc_types::convert_u8(self.game_mode));
+            data.append(&mut mc_types::convert_i8(self.previous_game_mode));
+            data.append(&mut String::try_convert_array(
+                &self.dimension_names)?);
+            data.append(&mut nbt::convert_named(
+                "", &nbt::Tag::Compound(self.registry_codec.clone()))?);
+            data.append(&mut mc_types::convert_string(
+                &self.dimension_type_name));
+            data.append(&mut mc_types::convert_string(&self.dimension_name));
+            data.append(&mut mc_types::convert_i64(self.hashed_seed));
+            data.append(&mut mc_types::convert_var_int(self.max_players));
+            data.append(&mut mc_types::convert_var_int(self.view_distance));
+            data.append(&mut mc_types::convert_var_int(
+                self.simulation_distance));
+            data.append(&mut mc_types::convert_bool(self.reduced_debug_info));
+            data.append(&mut mc_types::convert_bool(
+                self.enable_respawn_screen));
+            data.append(&mut mc_types::convert_bool(self.is_debug));
+            data.append(&mut mc_types::convert_bool(self.is_flat));
+            self.convert_death_location(data)?;
+            data.append(&mut mc_types::convert_var_int(self.portal_cooldown));
+            Ok(())
+        }
+
+        fn convert_death_location(&self, data: &mut Vec<u8>) -> Result<()> {
+            match &self.death_location {
+                Some(location) => {
+                    data.append(&mut mc_types::convert_bool(true));
+                    data.append(&mut location.try_convert()?);
+                },
+                None => data.append(&mut mc_types::convert_bool(false)),
+            }
+            Ok(())
+        }
+    }
+
+    fn get_death_location(data: &mut &[u8]) -> Result<Option<DeathLocation>> {
+        if mc_types::get_bool(data).field("death_location")? {
+            Ok(Some(DeathLocation::get(data).field("death_location")?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[derive(Clone, PacketArray)]
+    pub struct DeathLocation {
+        pub dimension_name: String,
+        pub location: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x35,
+        ids(V1_20_1 = 0x32, V1_20_2 = 0x33, V1_20_3 = 0x33))]
+    pub struct Ping {
+        pub id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x36, since = V1_20_2,
+        ids(V1_20_2 = 0x34, V1_20_3 = 0x34))]
+    pub struct PingResponse {
+        pub payload: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x38,
+        ids(V1_20_1 = 0x34, V1_20_2 = 0x36, V1_20_3 = 0x36))]
+    pub struct PlayerAbilities {
+        pub flags: i8,
+        pub flying_speed: f32,
+        pub field_of_view_modifier: f32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x40,
+        ids(V1_20_1 = 0x3C, V1_20_2 = 0x3E, V1_20_3 = 0x3E))]
+    pub struct SyncPlayerPosition {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        pub yaw: f32,
+        pub pitch: f32,
+        pub flags: i8,
+        #[packet(var_int)]
+        pub teleport_id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x42,
+        ids(V1_20_1 = 0x3E, V1_20_2 = 0x40, V1_20_3 = 0x40))]
+    pub struct RemoveEntities {
+        #[packet(var_int)]
+        pub entity_ids: Vec<i32>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x4C,
+        ids(V1_20_1 = 0x46, V1_20_2 = 0x48, V1_20_3 = 0x4A))]
+    pub struct SetActionBarText {
+        pub text: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x53,
+        ids(V1_20_1 = 0x4D, V1_20_2 = 0x4F, V1_20_3 = 0x51))]
+    pub struct SetHeldItem {
+        pub slot: i8,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x54,
+        ids(V1_20_1 = 0x4E, V1_20_2 = 0x50, V1_20_3 = 0x52))]
+    pub struct SetCenterChunk {
+        #[packet(var_int)]
+        pub chunk_x: i32,
+        #[packet(var_int)]
+        pub chunk_z: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x55,
+        ids(V1_20_1 = 0x4F, V1_20_2 = 0x51, V1_20_3 = 0x53))]
+    pub struct SetRenderDistance {
+        #[packet(var_int)]
+        pub view_distance: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x56,
+        ids(V1_20_1 = 0x50, V1_20_2 = 0x52, V1_20_3 = 0x54))]
+    pub struct SetDefaultSpawnPosition {
+        pub location: i64,
+        pub angle: f32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x5C,
+        ids(V1_20_1 = 0x56, V1_20_2 = 0x58, V1_20_3 = 0x5A))]
+    pub struct SetExperience {
+        pub experience_bar: f32,
+        #[packet(var_int)]
+        pub level: i32,
+        #[packet(var_int)]
+        pub total_experience: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x5D,
+        ids(V1_20_1 = 0x57, V1_20_2 = 0x59, V1_20_3 = 0x5B))]
+    pub struct SetHealth {
+        pub health: f32,
+        #[packet(var_int)]
+        pub food: i32,
+        pub food_saturation: f32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x62,
+        ids(V1_20_1 = 0x5C, V1_20_2 = 0x5E, V1_20_3 = 0x60))]
+    pub struct SetSimulationDistance {
+        #[packet(var_int)]
+        pub simulation_distance: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x63,
+        ids(V1_20_1 = 0x5D, V1_20_2 = 0x5F, V1_20_3 = 0x61))]
+    pub struct SetSubtitleText {
+        pub subtitle_text: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x64,
+        ids(V1_20_1 = 0x5E, V1_20_2 = 0x60, V1_20_3 = 0x62))]
+    pub struct UpdateTime {
+        pub world_age: i64,
+        pub time_of_day: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x65,
+        ids(V1_20_1 = 0x5F, V1_20_2 = 0x61, V1_20_3 = 0x63))]
+    pub struct SetTitleText {
+        pub title_text: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x69, since = V1_20_2,
+        ids(V1_20_2 = 0x65, V1_20_3 = 0x67))]
+    pub struct StartConfiguration {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x6B, since = V1_20_5)]
+    pub struct StoreCookie {
+        pub key: String,
+        #[packet(max_length = 5120)]
+        pub payload: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x6C,
+        ids(V1_20_1 = 0x64, V1_20_2 = 0x67, V1_20_3 = 0x69))]
+    pub struct SystemChat {
+        pub content: TextComponent,
+        pub overlay: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x6D,
+        ids(V1_20_1 = 0x65, V1_20_2 = 0x68, V1_20_3 = 0x6A))]
+    pub struct SetTabListHeaderAndFooter {
+        pub header: TextComponent,
+        pub footer: TextComponent,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x71, since = V1_20_3,
+        ids(V1_20_3 = 0x6E))]
+    pub struct SetTickingState {
+        pub tick_rate: f32,
+        pub is_frozen: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x72, since = V1_20_3,
+        ids(V1_20_3 = 0x6F))]
+    pub struct StepTick {
+        #[packet(var_int)]
+        pub tick_steps: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x73, since = V1_20_5)]
+    pub struct Transfer {
+        pub host: String,
+        #[packet(var_int)]
+        pub port: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x78,
+        ids(V1_20_1 = 0x6E, V1_20_2 = 0x70, V1_20_3 = 0x74))]
+    pub struct UpdateTags {
+        pub registries: Vec<RegistryTags>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x7A, since = V1_21)]
+    pub struct CustomReportDetails {
+        #[packet(max_length = 32)]
+        pub details: Vec<ReportDetail>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x7B, since = V1_21)]
+    pub struct ServerLinks {
+        #[packet(remaining)]
+        pub links: Vec<u8>,
     }
-}
 
-pub struct PlayPacket {
-    pub data: Vec<u8>
 }
 
-impl Packet for PlayPacket {
+pub mod serverbound {
+
+    use crate::mc_types::Packet;
+
+    crate::packet_enum! {
+        #[allow(clippy::large_enum_variant)]
+        pub enum Play (Play, Serverbound) {
+            ConfirmTeleportation(ConfirmTeleportation),
+            ChatCommand(ChatCommand),
+            ChatMessage(ChatMessage),
+            ChunkBatchReceived(ChunkBatchReceived),
+            ClientStatus(ClientStatus),
+            ClientInformation(ClientInformation),
+            AcknowledgeConfiguration(AcknowledgeConfiguration),
+            CloseContainer(CloseContainer),
+            CookieResponse(CookieResponse),
+            PluginMessage(PluginMessage),
+            KeepAlive(KeepAlive),
+            SetPlayerPosition(SetPlayerPosition),
+            SetPlayerPositionAndRotation(SetPlayerPositionAndRotation),
+            SetPlayerRotation(SetPlayerRotation),
+            SetPlayerOnGround(SetPlayerOnGround),
+            PingRequest(PingRequest),
+            PlayerCommand(PlayerCommand),
+            Pong(Pong),
+            ResourcePackResponse(ResourcePackResponse),
+            SetHeldItem(SetHeldItem),
+            SwingArm(SwingArm),
+            UseItem(UseItem),
+            _ => Unknown,
+        }
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x00)]
+    pub struct ConfirmTeleportation {
+        #[packet(var_int)]
+        pub teleport_id: i32,
+    }
 
-    fn packet_id() -> i32 {0}
+    #[derive(Packet)]
+    #[packet(id = 0x04, since = V1_20_5)]
+    pub struct ChatCommand {
+        pub command: String,
+    }
 
-    fn get(data: &mut Vec<u8>) -> Result<Self> {
-        Ok(Self {
-            data: data.clone()
-        })
+    #[derive(Packet)]
+    #[packet(id = 0x06,
+        ids(V1_20_1 = 0x05, V1_20_2 = 0x05, V1_20_3 = 0x05))]
+    pub struct ChatMessage {
+        #[packet(max_length = 256)]
+        pub message: String,
+        pub timestamp: i64,
+        pub salt: i64,
+        pub signature: Option<[u8; 256]>,
+        #[packet(var_int)]
+        pub message_count: i32,
+        pub acknowledged: [u8; 3],
     }
 
-    fn convert(&self) -> Vec<u8> {
-        let mut data: Vec<u8> = vec![];
-        data.append(&mut self.data.clone());
+    #[derive(Packet)]
+    #[packet(id = 0x08, since = V1_20_2,
+        ids(V1_20_2 = 0x07, V1_20_3 = 0x07))]
+    pub struct ChunkBatchReceived {
+        pub chunks_per_tick: f32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x09,
+        ids(V1_20_1 = 0x07, V1_20_2 = 0x08, V1_20_3 = 0x08))]
+    pub struct ClientStatus {
+        #[packet(var_int)]
+        pub action_id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0A,
+        ids(V1_20_1 = 0x08, V1_20_2 = 0x09, V1_20_3 = 0x09))]
+    pub struct ClientInformation {
+        #[packet(max_length = 16)]
+        pub locale: String,
+        pub view_distance: i8,
+        #[packet(var_int)]
+        pub chat_mode: i32,
+        pub chat_colors: bool,
+        pub displayed_skin_parts: u8,
+        #[packet(var_int)]
+        pub main_hand: i32,
+        pub enable_text_filtering: bool,
+        pub allow_server_listings: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x0C, since = V1_20_2,
+        ids(V1_20_2 = 0x0B, V1_20_3 = 0x0B))]
+    pub struct AcknowledgeConfiguration {}
+
+    #[derive(Packet)]
+    #[packet(id = 0x0F,
+        ids(V1_20_1 = 0x0C, V1_20_2 = 0x0E, V1_20_3 = 0x0E))]
+    pub struct CloseContainer {
+        pub window_id: u8,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x11, since = V1_20_5)]
+    pub struct CookieResponse {
+        pub key: String,
+        #[packet(max_length = 5120)]
+        pub payload: Option<Vec<u8>>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x12,
+        ids(V1_20_1 = 0x0D, V1_20_2 = 0x0F, V1_20_3 = 0x10))]
+    pub struct PluginMessage {
+        pub channel: String,
+        #[packet(remaining)]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x18,
+        ids(V1_20_1 = 0x12, V1_20_2 = 0x14, V1_20_3 = 0x15))]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x1A,
+        ids(V1_20_1 = 0x14, V1_20_2 = 0x16, V1_20_3 = 0x17))]
+    pub struct SetPlayerPosition {
+        pub x: f64,
+        pub feet_y: f64,
+        pub z: f64,
+        pub on_ground: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x1B,
+        ids(V1_20_1 = 0x15, V1_20_2 = 0x17, V1_20_3 = 0x18))]
+    pub struct SetPlayerPositionAndRotation {
+        pub x: f64,
+        pub feet_y: f64,
+        pub z: f64,
+        pub yaw: f32,
+        pub pitch: f32,
+        pub on_ground: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x1C,
+        ids(V1_20_1 = 0x16, V1_20_2 = 0x18, V1_20_3 = 0x19))]
+    pub struct SetPlayerRotation {
+        pub yaw: f32,
+        pub pitch: f32,
+        pub on_ground: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x1D,
+        ids(V1_20_1 = 0x17, V1_20_2 = 0x19, V1_20_3 = 0x1A))]
+    pub struct SetPlayerOnGround {
+        pub on_ground: bool,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x21, since = V1_20_2,
+        ids(V1_20_2 = 0x1D, V1_20_3 = 0x1E))]
+    pub struct PingRequest {
+        pub payload: i64,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x25,
+        ids(V1_20_1 = 0x1E, V1_20_2 = 0x21, V1_20_3 = 0x22))]
+    pub struct PlayerCommand {
+        #[packet(var_int)]
+        pub entity_id: i32,
+        #[packet(var_int)]
+        pub action_id: i32,
+        #[packet(var_int)]
+        pub jump_boost: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x27,
+        ids(V1_20_1 = 0x20, V1_20_2 = 0x23, V1_20_3 = 0x24))]
+    pub struct Pong {
+        pub id: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x2B,
+        ids(V1_20_1 = 0x24, V1_20_2 = 0x27, V1_20_3 = 0x28))]
+    pub struct ResourcePackResponse {
+        #[packet(since = V1_20_3)]
+        pub uuid: u128,
+        #[packet(var_int)]
+        pub result: i32,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x2F,
+        ids(V1_20_1 = 0x28, V1_20_2 = 0x2B, V1_20_3 = 0x2C))]
+    pub struct SetHeldItem {
+        pub slot: i16,
+    }
+
+    #[derive(Packet)]
+    #[packet(id = 0x36,
+        ids(V1_20_1 = 0x2F, V1_20_2 = 0x32, V1_20_3 = 0x33))]
+    pub struct SwingArm {
+        #[packet(var_int)]
+        pub hand: i32,
+    }
 
-        data
+    #[derive(Packet)]
+    #[packet(id = 0x39,
+        ids(V1_20_1 = 0x32, V1_20_2 = 0x35, V1_20_3 = 0x36))]
+    pub struct UseItem {
+        #[packet(var_int)]
+        pub hand: i32,
+        #[packet(var_int)]
+        pub sequence: i32,
+        #[packet(since = V1_21)]
+        pub yaw: f32,
+        #[packet(since = V1_21)]
+        pub pitch: f32,
     }
 
 }
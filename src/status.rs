@@ -1,12 +1,153 @@
 // Yeahbut December 2023
 
+use std::io;
+
+use serde_json::Map;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+use crate::mc_types::{
+    self, Packet, PacketError, ProtocolConnection, ProtocolError, Result};
+use crate::favicon;
+use crate::legacy_ping::LegacyPingResponse;
+use crate::text::TextComponent;
+
+pub struct StatusResponder {
+    data: clientbound::StatusResponseData,
+}
+
+impl StatusResponder {
+    pub fn new() -> Self {
+        Self::from_data(clientbound::StatusResponseData {
+            version: clientbound::StatusVersion {
+                name: mc_types::VERSION_NAME.to_string(),
+                protocol: mc_types::VERSION_PROTOCOL,
+            },
+            description: clientbound::StatusDescription::Chat(
+                TextComponent::text("A Minecraft Server")),
+            players: clientbound::StatusPlayers {
+                max: 20,
+                online: 0,
+                sample: None,
+            },
+            favicon: None,
+            enforcesSecureChat: None,
+            previewsChat: None,
+            preventsChatReports: None,
+            forgeData: None,
+            modinfo: None,
+            extra: Map::new(),
+        })
+    }
+
+    pub fn from_data(data: clientbound::StatusResponseData) -> Self {
+        Self { data }
+    }
+
+    pub fn motd(mut self, motd: impl Into<TextComponent>) -> Self {
+        self.data.description =
+            clientbound::StatusDescription::Chat(motd.into());
+        self
+    }
+
+    pub fn version(mut self, name: impl Into<String>, protocol: i32) -> Self {
+        self.data.version = clientbound::StatusVersion {
+            name: name.into(),
+            protocol,
+        };
+        self
+    }
+
+    pub fn players(mut self, online: i32, max: i32) -> Self {
+        self.data.players.online = online;
+        self.data.players.max = max;
+        self
+    }
+
+    pub fn sample(
+        mut self,
+        sample: Vec<clientbound::StatusPlayerInfo>,
+    ) -> Self {
+        self.data.players.sample = Some(sample);
+        self
+    }
+
+    /// Takes a data URI from `favicon::from_png` or `favicon::from_file`,
+    /// rejecting anything that is not a 64x64 PNG.
+    pub fn favicon(mut self, favicon: impl Into<String>) -> Result<Self> {
+        let favicon = favicon.into();
+        let size = favicon::png_dimensions(&favicon::to_png(&favicon)?)?;
+        if size != (favicon::FAVICON_SIZE, favicon::FAVICON_SIZE) {
+            return Err(
+                PacketError::InvalidFavicon("image is not 64x64").into());
+        }
+        self.data.favicon = Some(favicon);
+        Ok(self)
+    }
+
+    pub fn enforces_secure_chat(mut self, enforces: bool) -> Self {
+        self.data.enforcesSecureChat = Some(enforces);
+        self
+    }
+
+    pub fn data(&self) -> &clientbound::StatusResponseData {
+        &self.data
+    }
+
+    pub fn legacy_response(&self) -> LegacyPingResponse {
+        LegacyPingResponse::from_status(&self.data)
+    }
+
+    /// Answers the status request and ping after a handshake with
+    /// next_state=1, then closes the connection.
+    pub async fn respond<R, W>(
+        &self,
+        conn: &mut ProtocolConnection<R, W>,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        match serverbound::StatusPackets::read(conn).await? {
+            serverbound::StatusPackets::Status(_) => {},
+            packet => return Err(
+                PacketError::InvalidPacketId(packet.packet_id()).into()),
+        }
+        clientbound::Status {
+            response: serde_json::to_string(&self.data)?,
+        }.write(conn).await?;
+
+        match serverbound::StatusPackets::read(conn).await {
+            Ok(serverbound::StatusPackets::Ping(ping)) =>
+                clientbound::Ping { payload: ping.payload }
+                    .write(conn).await?,
+            Ok(packet) => return Err(
+                PacketError::InvalidPacketId(packet.packet_id()).into()),
+            // Some clients disconnect without pinging
+            Err(ProtocolError::Io(error))
+                if error.kind() == io::ErrorKind::UnexpectedEof => {},
+            Err(error) => return Err(error),
+        }
+        conn.stream_write.shutdown().await?;
+        Ok(())
+    }
+}
+
+impl Default for StatusResponder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub mod clientbound {
 
     use serde::{Serialize, Deserialize, Serializer, Deserializer};
-    use serde::de::{self, Visitor, MapAccess};
+    use serde::de::{self, Visitor, MapAccess, SeqAccess};
+    use serde::ser::SerializeMap;
+    use serde_json::{Map, Value};
     use std::fmt;
 
-    use crate::mc_types::{self, Result, Packet, PacketError};
+    use crate::mc_types::{self, Result, Packet};
+    use crate::text;
 
     #[derive(Serialize, Deserialize)]
     pub struct StatusVersion {
@@ -14,11 +155,34 @@ pub mod clientbound {
         pub protocol: i32,
     }
 
+    #[allow(clippy::large_enum_variant)]
     pub enum StatusDescription {
         String(String),
         Chat(mc_types::Chat),
     }
 
+    impl StatusDescription {
+        pub fn to_component(&self) -> mc_types::Chat {
+            match self {
+                StatusDescription::String(s) =>
+                    mc_types::Chat::from_legacy(s),
+                StatusDescription::Chat(c) => c.clone(),
+            }
+        }
+
+        pub fn upgrade(self) -> Self {
+            match self {
+                StatusDescription::String(s) => StatusDescription::Chat(
+                    mc_types::Chat::from_legacy(&s)),
+                chat => chat,
+            }
+        }
+
+        pub fn to_legacy(&self, hex: bool) -> String {
+            self.to_component().to_legacy_with(text::SECTION_SIGN, hex)
+        }
+    }
+
     impl Serialize for StatusDescription {
         fn serialize<S>(&self, serializer: S)
             -> std::result::Result<S::Ok, S::Error>
@@ -27,6 +191,12 @@ pub mod clientbound {
         {
             match *self {
                 StatusDescription::String(ref s) => serializer.serialize_str(s),
+                // Keep the object form so a parsed description is unchanged
+                StatusDescription::Chat(ref c) if c.is_plain() => {
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry("text", &c.to_plain_text())?;
+                    map.end()
+                },
                 StatusDescription::Chat(ref c) => c.serialize(serializer),
             }
         }
@@ -46,7 +216,7 @@ pub mod clientbound {
                     -> fmt::Result
                 {
                     formatter.write_str(
-                        "a string or a map representing a Chat object")
+                        "a string, an array or a map for a Chat object")
                 }
 
                 fn visit_str<E>(self, value: &str)
@@ -66,6 +236,16 @@ pub mod clientbound {
                         de::value::MapAccessDeserializer::new(map))?;
                     Ok(StatusDescription::Chat(chat))
                 }
+
+                fn visit_seq<A>(self, seq: A)
+                    -> std::result::Result<StatusDescription, A::Error>
+                where
+                    A: SeqAccess<'de>,
+                {
+                    let chat = mc_types::Chat::deserialize(
+                        de::value::SeqAccessDeserializer::new(seq))?;
+                    Ok(StatusDescription::Chat(chat))
+                }
             }
 
             deserializer.deserialize_any(StatusDescriptionVisitor)
@@ -74,8 +254,8 @@ pub mod clientbound {
 
     #[derive(Serialize, Deserialize)]
     pub struct StatusPlayerInfo {
-        pub name: String,
         pub id: String,
+        pub name: String,
     }
 
     #[derive(Serialize, Deserialize)]
@@ -86,41 +266,100 @@ pub mod clientbound {
         pub sample: Option<Vec<StatusPlayerInfo>>
     }
 
+    #[derive(Serialize, Deserialize)]
+    pub struct ForgeChannel {
+        pub res: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub required: Option<bool>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct ForgeMod {
+        #[serde(rename = "modId")]
+        pub mod_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modmarker: Option<String>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    /// Sent by Forge and NeoForge servers since 1.13.
+    #[derive(Serialize, Deserialize)]
+    pub struct ForgeData {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub channels: Option<Vec<ForgeChannel>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub mods: Option<Vec<ForgeMod>>,
+        #[serde(
+            rename = "fmlNetworkVersion",
+            skip_serializing_if = "Option::is_none",
+        )]
+        pub fml_network_version: Option<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub truncated: Option<bool>,
+        // Channels and mods packed into a string since 1.18
+        #[serde(rename = "d", skip_serializing_if = "Option::is_none")]
+        pub data: Option<String>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct ModInfoMod {
+        pub modid: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    /// Sent by Forge servers before 1.13.
+    #[derive(Serialize, Deserialize)]
+    pub struct ModInfo {
+        #[serde(rename = "type")]
+        pub mod_type: String,
+        #[serde(rename = "modList", default)]
+        pub mod_list: Vec<ModInfoMod>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
     #[allow(non_snake_case)]
     #[derive(Serialize, Deserialize)]
     pub struct StatusResponseData {
-        pub version: StatusVersion,
+        // In the order vanilla writes them
         pub description: StatusDescription,
         pub players: StatusPlayers,
+        pub version: StatusVersion,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub favicon: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub enforcesSecureChat: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub previewsChat: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub preventsChatReports: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub forgeData: Option<ForgeData>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modinfo: Option<ModInfo>,
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
     }
 
-    pub enum StatusPackets {
-        Status(Status),
-        Ping(Ping),
-    }
-
-    impl StatusPackets {
-        pub async fn read<T: mc_types::ProtocolRead>(
-            conn: &mut T,
-        ) -> Result<Self> {
-            let mut data = conn.read_data().await?;
-            let packet_id = mc_types::get_var_int(&mut data)?;
-            if packet_id == Status::packet_id() {
-                return Ok(Self::Status(Status::get(&mut data)?))
-            } else if packet_id == Ping::packet_id() {
-                return Ok(Self::Ping(Ping::get(&mut data)?))
-            } else {
-                return Err(Box::new(PacketError::InvalidPacketId))
-            }
+    crate::packet_enum! {
+        pub enum StatusPackets (Status, Clientbound) {
+            Status(Status),
+            Ping(Ping),
         }
     }
 
+    #[derive(Packet)]
+    #[packet(id = 0)]
     pub struct Status {
         pub response: String
     }
@@ -139,118 +378,33 @@ pub mod clientbound {
 
     }
 
-    impl Packet for Status {
-
-        fn packet_id() -> i32 {0}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                response: mc_types::get_string(&mut data)?
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_string(&self.response));
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 1)]
     pub struct Ping {
         pub payload: i64
     }
 
-    impl Packet for Ping {
-
-        fn packet_id() -> i32 {1}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                payload: mc_types::get_i64(&mut data)
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_i64(self.payload));
-
-            data
-        }
-
-    }
-
 }
 
 pub mod serverbound {
 
-    use crate::mc_types::{self, Result, Packet, PacketError};
+    use crate::mc_types::Packet;
 
-    pub enum StatusPackets {
-        Status(Status),
-        Ping(Ping),
-    }
-
-    impl StatusPackets {
-        pub async fn read<T: mc_types::ProtocolRead>(
-            conn: &mut T,
-        ) -> Result<Self> {
-            let mut data = conn.read_data().await?;
-            let packet_id = mc_types::get_var_int(&mut data)?;
-            if packet_id == Status::packet_id() {
-                return Ok(Self::Status(Status::get(&mut data)?))
-            } else if packet_id == Ping::packet_id() {
-                return Ok(Self::Ping(Ping::get(&mut data)?))
-            } else {
-                return Err(Box::new(PacketError::InvalidPacketId))
-            }
+    crate::packet_enum! {
+        pub enum StatusPackets (Status, Serverbound) {
+            Status(Status),
+            Ping(Ping),
         }
     }
 
+    #[derive(Packet)]
+    #[packet(id = 0)]
     pub struct Status {}
 
-    impl Packet for Status {
-
-        fn packet_id() -> i32 {0}
-
-        fn get(_data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {})
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-
-            data
-        }
-
-    }
-
+    #[derive(Packet)]
+    #[packet(id = 1)]
     pub struct Ping {
         pub payload: i64
     }
 
-    impl Packet for Ping {
-
-        fn packet_id() -> i32 {1}
-
-        fn get(mut data: &mut Vec<u8>) -> Result<Self> {
-            Ok(Self {
-                payload: mc_types::get_i64(&mut data)
-            })
-        }
-
-        fn convert(&self) -> Vec<u8> {
-            let mut data: Vec<u8> = vec![];
-            data.append(&mut mc_types::convert_var_int(Self::packet_id()));
-            data.append(&mut mc_types::convert_i64(self.payload));
-
-            data
-        }
-
-    }
-
 }
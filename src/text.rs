@@ -0,0 +1,892 @@
+// Yeahbut October 2026
+
+use std::fmt;
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::{self, Visitor, MapAccess, SeqAccess};
+use serde_json::{Map, Value};
+
+use crate::mc_types::{self, PacketError};
+use crate::nbt::{Compound, Tag};
+
+const BOOLEAN_KEYS: &[&str] = &[
+    "bold", "italic", "underlined", "strikethrough", "obfuscated", "interpret",
+];
+
+pub const SECTION_SIGN: char = '\u{a7}';
+
+const LEGACY_COLORS: [(char, &str, u32); 16] = [
+    ('0', "black", 0x000000),
+    ('1', "dark_blue", 0x0000AA),
+    ('2', "dark_green", 0x00AA00),
+    ('3', "dark_aqua", 0x00AAAA),
+    ('4', "dark_red", 0xAA0000),
+    ('5', "dark_purple", 0xAA00AA),
+    ('6', "gold", 0xFFAA00),
+    ('7', "gray", 0xAAAAAA),
+    ('8', "dark_gray", 0x555555),
+    ('9', "blue", 0x5555FF),
+    ('a', "green", 0x55FF55),
+    ('b', "aqua", 0x55FFFF),
+    ('c', "red", 0xFF5555),
+    ('d', "light_purple", 0xFF55FF),
+    ('e', "yellow", 0xFFFF55),
+    ('f', "white", 0xFFFFFF),
+];
+
+// Obfuscated, bold, strikethrough, underlined, italic
+const LEGACY_FORMATS: [char; 5] = ['k', 'l', 'm', 'n', 'o'];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextComponent {
+    pub content: Content,
+    pub style: Style,
+    pub extra: Vec<TextComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    Text(String),
+    Translatable {
+        translate: String,
+        fallback: Option<String>,
+        with: Vec<TextComponent>,
+    },
+    Score {
+        name: String,
+        objective: String,
+        value: Option<String>,
+    },
+    Selector {
+        selector: String,
+        separator: Option<Box<TextComponent>>,
+    },
+    Keybind(String),
+    Nbt {
+        nbt: String,
+        interpret: Option<bool>,
+        separator: Option<Box<TextComponent>>,
+        source: NbtSource,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtSource {
+    Block(String),
+    Entity(String),
+    Storage(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Style {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlined: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfuscated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insertion: Option<String>,
+    #[serde(rename = "clickEvent", skip_serializing_if = "Option::is_none")]
+    pub click_event: Option<ClickEvent>,
+    #[serde(rename = "hoverEvent", skip_serializing_if = "Option::is_none")]
+    pub hover_event: Option<HoverEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    OpenUrl,
+    OpenFile,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+    CopyToClipboard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoverEvent {
+    ShowText(Box<TextComponent>),
+    ShowItem(HoverItem),
+    ShowEntity(HoverEntity),
+    Legacy {
+        action: String,
+        value: Box<TextComponent>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoverItem {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    // Keeps other forms, such as the capitalised "Count" before 1.20.5
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoverEntity {
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    pub id: EntityId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Box<TextComponent>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EntityId {
+    String(String),
+    IntArray([i32; 4]),
+}
+
+impl TextComponent {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::from_content(Content::Text(text.into()))
+    }
+
+    pub fn translatable(
+        translate: impl Into<String>,
+        with: Vec<TextComponent>,
+    ) -> Self {
+        Self::from_content(Content::Translatable {
+            translate: translate.into(),
+            fallback: None,
+            with,
+        })
+    }
+
+    pub fn keybind(keybind: impl Into<String>) -> Self {
+        Self::from_content(Content::Keybind(keybind.into()))
+    }
+
+    pub fn from_content(content: Content) -> Self {
+        Self {
+            content,
+            style: Style::default(),
+            extra: vec![],
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn append(mut self, child: TextComponent) -> Self {
+        self.extra.push(child);
+        self
+    }
+
+    pub fn is_plain(&self) -> bool {
+        matches!(self.content, Content::Text(_)) &&
+            self.style == Style::default() &&
+            self.extra.is_empty()
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        self.push_plain_text(&mut text);
+        text
+    }
+
+    pub fn to_nbt(&self) -> mc_types::Result<Tag> {
+        json_to_nbt(&serde_json::to_value(self)?).ok_or_else(||
+            PacketError::InvalidNbt("invalid text component").into())
+    }
+
+    pub fn from_nbt(tag: &Tag) -> mc_types::Result<Self> {
+        serde_json::from_value(nbt_to_json(tag, false)).map_err(|_|
+            PacketError::InvalidNbt("invalid text component").into())
+    }
+
+    pub fn from_legacy(text: &str) -> Self {
+        Self::from_legacy_with(text, SECTION_SIGN)
+    }
+
+    pub fn from_legacy_with(text: &str, marker: char) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut parts: Vec<TextComponent> = vec![];
+        let mut style = Style::default();
+        let mut current = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == marker {
+                if let Some((next, length)) =
+                    legacy_code(&chars[i + 1..], marker, &style)
+                {
+                    if next != style {
+                        push_legacy_part(&mut parts, &mut current, &style);
+                        style = next;
+                    }
+                    i += length + 1;
+                    continue;
+                }
+            }
+            current.push(chars[i]);
+            i += 1;
+        }
+        push_legacy_part(&mut parts, &mut current, &style);
+        match parts.len() {
+            0 => Self::default(),
+            1 => parts.remove(0),
+            _ => Self {
+                extra: parts,
+                ..Self::default()
+            },
+        }
+    }
+
+    pub fn to_legacy(&self) -> String {
+        self.to_legacy_with(SECTION_SIGN, true)
+    }
+
+    /// Colors without a legacy code are rounded to the nearest named color
+    /// unless `hex` is set, which pre-1.16 clients don't understand.
+    pub fn to_legacy_with(&self, marker: char, hex: bool) -> String {
+        let mut legacy = String::new();
+        let mut current = LegacyStyle::default();
+        self.push_legacy(
+            &mut legacy, &LegacyStyle::default(), &mut current, marker, hex);
+        legacy
+    }
+
+    fn content_text(&self) -> &str {
+        match &self.content {
+            Content::Text(value) => value,
+            Content::Translatable { translate, fallback, .. } =>
+                fallback.as_ref().unwrap_or(translate),
+            Content::Score { value: Some(value), .. } => value,
+            Content::Selector { selector, .. } => selector,
+            Content::Keybind(keybind) => keybind,
+            _ => "",
+        }
+    }
+
+    fn push_plain_text(&self, text: &mut String) {
+        text.push_str(self.content_text());
+        for child in &self.extra {
+            child.push_plain_text(text);
+        }
+    }
+
+    fn push_legacy(
+        &self,
+        legacy: &mut String,
+        parent: &LegacyStyle,
+        current: &mut LegacyStyle,
+        marker: char,
+        hex: bool,
+    ) {
+        let style = LegacyStyle::inherit(&self.style, parent, hex);
+        let text = self.content_text();
+        if !text.is_empty() {
+            style.push_codes(legacy, current, marker);
+            legacy.push_str(text);
+        }
+        for child in &self.extra {
+            child.push_legacy(legacy, &style, current, marker, hex);
+        }
+    }
+}
+
+impl Default for TextComponent {
+    fn default() -> Self {
+        Self::text("")
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for TextComponent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LegacyColor {
+    Named(usize),
+    Rgb(u32),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct LegacyStyle {
+    color: Option<LegacyColor>,
+    formats: [bool; 5],
+}
+
+impl LegacyStyle {
+    fn inherit(style: &Style, parent: &LegacyStyle, hex: bool) -> Self {
+        let color = match style.color.as_deref() {
+            None => parent.color,
+            Some("reset") => None,
+            Some(color) => legacy_color(color, hex).or(parent.color),
+        };
+        let flags = [
+            style.obfuscated,
+            style.bold,
+            style.strikethrough,
+            style.underlined,
+            style.italic,
+        ];
+        let mut formats = parent.formats;
+        for (format, flag) in formats.iter_mut().zip(flags) {
+            *format = flag.unwrap_or(*format);
+        }
+        Self { color, formats }
+    }
+
+    fn push_codes(
+        &self,
+        legacy: &mut String,
+        current: &mut LegacyStyle,
+        marker: char,
+    ) {
+        if self == current {
+            return;
+        }
+        let removes_format = current.formats.iter()
+            .zip(self.formats)
+            .any(|(current, next)| *current && !next);
+        if self.color != current.color || removes_format {
+            // Color codes reset formatting in legacy text
+            match self.color {
+                Some(LegacyColor::Named(index)) => {
+                    legacy.push(marker);
+                    legacy.push(LEGACY_COLORS[index].0);
+                },
+                Some(LegacyColor::Rgb(rgb)) => {
+                    legacy.push(marker);
+                    legacy.push('x');
+                    for digit in format!("{:06x}", rgb).chars() {
+                        legacy.push(marker);
+                        legacy.push(digit);
+                    }
+                },
+                None => {
+                    legacy.push(marker);
+                    legacy.push('r');
+                },
+            }
+            current.formats = [false; 5];
+        }
+        for (i, code) in LEGACY_FORMATS.iter().enumerate() {
+            if self.formats[i] && !current.formats[i] {
+                legacy.push(marker);
+                legacy.push(*code);
+            }
+        }
+        *current = *self;
+    }
+}
+
+fn legacy_color(color: &str, hex: bool) -> Option<LegacyColor> {
+    if let Some(index) = LEGACY_COLORS.iter()
+        .position(|(_, name, _)| *name == color)
+    {
+        return Some(LegacyColor::Named(index));
+    }
+    let digits = color.strip_prefix('#')?;
+    if digits.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(digits, 16).ok()?;
+    if hex {
+        return Some(LegacyColor::Rgb(rgb));
+    }
+    let channels = |rgb: u32| [rgb >> 16, (rgb >> 8) & 0xFF, rgb & 0xFF];
+    LEGACY_COLORS.iter()
+        .enumerate()
+        .min_by_key(|(_, (_, _, named))| channels(rgb).iter()
+            .zip(channels(*named))
+            .map(|(a, b)| a.abs_diff(b).pow(2))
+            .sum::<u32>())
+        .map(|(index, _)| LegacyColor::Named(index))
+}
+
+fn legacy_code(
+    rest: &[char],
+    marker: char,
+    style: &Style,
+) -> Option<(Style, usize)> {
+    let code = rest.first()?.to_ascii_lowercase();
+    if let Some((_, name, _)) = LEGACY_COLORS.iter()
+        .find(|(legacy, _, _)| *legacy == code)
+    {
+        return Some((Style {
+            color: Some(name.to_string()),
+            ..Style::default()
+        }, 1));
+    }
+    let mut style = style.clone();
+    match code {
+        'x' => {
+            let digits = rest.get(1..13)?;
+            let mut color = String::from("#");
+            for pair in digits.chunks(2) {
+                if pair[0] != marker || !pair[1].is_ascii_hexdigit() {
+                    return None;
+                }
+                color.push(pair[1].to_ascii_lowercase());
+            }
+            return Some((Style {
+                color: Some(color),
+                ..Style::default()
+            }, 13));
+        },
+        'k' => style.obfuscated = Some(true),
+        'l' => style.bold = Some(true),
+        'm' => style.strikethrough = Some(true),
+        'n' => style.underlined = Some(true),
+        'o' => style.italic = Some(true),
+        'r' => style = Style::default(),
+        _ => return None,
+    }
+    Some((style, 1))
+}
+
+fn push_legacy_part(
+    parts: &mut Vec<TextComponent>,
+    text: &mut String,
+    style: &Style,
+) {
+    if text.is_empty() {
+        return;
+    }
+    match parts.last_mut() {
+        Some(TextComponent {
+            content: Content::Text(last),
+            style: last_style,
+            ..
+        }) if last_style == style => last.push_str(text),
+        _ => parts.push(
+            TextComponent::text(text.as_str()).with_style(style.clone())),
+    }
+    text.clear();
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawComponent {
+    #[serde(rename = "type", skip_serializing)]
+    kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fallback: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with: Option<Vec<TextComponent>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<RawScore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selector: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keybind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nbt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interpret: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    separator: Option<Box<TextComponent>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<String>,
+    #[serde(rename = "source", skip_serializing)]
+    source_kind: Option<String>,
+    #[serde(flatten)]
+    style: Style,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra: Option<Vec<TextComponent>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawScore {
+    name: String,
+    objective: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl RawComponent {
+    fn content_kind(&self) -> Option<&str> {
+        if let Some(kind) = &self.kind {
+            return Some(kind);
+        }
+        if self.text.is_some() {
+            Some("text")
+        } else if self.translate.is_some() {
+            Some("translatable")
+        } else if self.score.is_some() {
+            Some("score")
+        } else if self.selector.is_some() {
+            Some("selector")
+        } else if self.keybind.is_some() {
+            Some("keybind")
+        } else if self.nbt.is_some() {
+            Some("nbt")
+        } else {
+            None
+        }
+    }
+
+    fn nbt_source(&mut self) -> Option<NbtSource> {
+        match self.source_kind.as_deref() {
+            Some("block") => self.block.take().map(NbtSource::Block),
+            Some("entity") => self.entity.take().map(NbtSource::Entity),
+            Some("storage") => self.storage.take().map(NbtSource::Storage),
+            _ => self.block.take().map(NbtSource::Block)
+                .or_else(|| self.entity.take().map(NbtSource::Entity))
+                .or_else(|| self.storage.take().map(NbtSource::Storage)),
+        }
+    }
+
+    fn into_component<E: de::Error>(mut self) -> Result<TextComponent, E> {
+        let content = match self.content_kind() {
+            Some("text") => Content::Text(self.text.take()
+                .ok_or_else(|| de::Error::missing_field("text"))?),
+            Some("translatable") => Content::Translatable {
+                translate: self.translate.take()
+                    .ok_or_else(|| de::Error::missing_field("translate"))?,
+                fallback: self.fallback.take(),
+                with: self.with.take().unwrap_or_default(),
+            },
+            Some("score") => {
+                let score = self.score.take()
+                    .ok_or_else(|| de::Error::missing_field("score"))?;
+                Content::Score {
+                    name: score.name,
+                    objective: score.objective,
+                    value: score.value,
+                }
+            },
+            Some("selector") => Content::Selector {
+                selector: self.selector.take()
+                    .ok_or_else(|| de::Error::missing_field("selector"))?,
+                separator: self.separator.take(),
+            },
+            Some("keybind") => Content::Keybind(self.keybind.take()
+                .ok_or_else(|| de::Error::missing_field("keybind"))?),
+            Some("nbt") => Content::Nbt {
+                nbt: self.nbt.take()
+                    .ok_or_else(|| de::Error::missing_field("nbt"))?,
+                interpret: self.interpret,
+                separator: self.separator.take(),
+                source: self.nbt_source().ok_or_else(|| de::Error::custom(
+                    "nbt component requires a block, entity or storage"))?,
+            },
+            Some(kind) => return Err(de::Error::unknown_variant(
+                kind,
+                &["text", "translatable", "score", "selector", "keybind",
+                    "nbt"],
+            )),
+            None => return Err(de::Error::custom(
+                "text component has no content")),
+        };
+        Ok(TextComponent {
+            content,
+            style: self.style,
+            extra: self.extra.unwrap_or_default(),
+        })
+    }
+
+    fn from_component(component: &TextComponent) -> Self {
+        let mut raw = RawComponent {
+            style: component.style.clone(),
+            ..Default::default()
+        };
+        if !component.extra.is_empty() {
+            raw.extra = Some(component.extra.clone());
+        }
+        match &component.content {
+            Content::Text(text) => raw.text = Some(text.clone()),
+            Content::Translatable { translate, fallback, with } => {
+                raw.translate = Some(translate.clone());
+                raw.fallback = fallback.clone();
+                if !with.is_empty() {
+                    raw.with = Some(with.clone());
+                }
+            },
+            Content::Score { name, objective, value } => {
+                raw.score = Some(RawScore {
+                    name: name.clone(),
+                    objective: objective.clone(),
+                    value: value.clone(),
+                });
+            },
+            Content::Selector { selector, separator } => {
+                raw.selector = Some(selector.clone());
+                raw.separator = separator.clone();
+            },
+            Content::Keybind(keybind) => raw.keybind = Some(keybind.clone()),
+            Content::Nbt { nbt, interpret, separator, source } => {
+                raw.nbt = Some(nbt.clone());
+                raw.interpret = *interpret;
+                raw.separator = separator.clone();
+                match source {
+                    NbtSource::Block(block) => raw.block = Some(block.clone()),
+                    NbtSource::Entity(entity) =>
+                        raw.entity = Some(entity.clone()),
+                    NbtSource::Storage(storage) =>
+                        raw.storage = Some(storage.clone()),
+                }
+            },
+        }
+        raw
+    }
+}
+
+impl Serialize for TextComponent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.content {
+            Content::Text(text) if self.is_plain() =>
+                serializer.serialize_str(text),
+            _ => RawComponent::from_component(self).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TextComponent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TextComponentVisitor;
+
+        impl<'de> Visitor<'de> for TextComponentVisitor {
+            type Value = TextComponent;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter)
+                -> fmt::Result
+            {
+                formatter.write_str(
+                    "a string, an array or a map for a text component")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<TextComponent, E>
+            where
+                E: de::Error,
+            {
+                Ok(TextComponent::text(value))
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<TextComponent, E>
+            where
+                E: de::Error,
+            {
+                Ok(TextComponent::text(value.to_string()))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<TextComponent, E>
+            where
+                E: de::Error,
+            {
+                Ok(TextComponent::text(value.to_string()))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<TextComponent, E>
+            where
+                E: de::Error,
+            {
+                Ok(TextComponent::text(value.to_string()))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<TextComponent, E>
+            where
+                E: de::Error,
+            {
+                Ok(TextComponent::text(value.to_string()))
+            }
+
+            fn visit_seq<A>(self, mut seq: A)
+                -> Result<TextComponent, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut component: TextComponent = seq.next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                while let Some(sibling) = seq.next_element()? {
+                    component.extra.push(sibling);
+                }
+                Ok(component)
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<TextComponent, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                RawComponent::deserialize(
+                    de::value::MapAccessDeserializer::new(map))?
+                    .into_component()
+            }
+        }
+
+        deserializer.deserialize_any(TextComponentVisitor)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawHoverEvent {
+    action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contents: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<TextComponent>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawHoverItem {
+    Id(String),
+    Item(HoverItem),
+}
+
+impl Serialize for HoverEvent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        let (action, contents) = match self {
+            HoverEvent::ShowText(text) =>
+                ("show_text", serde_json::to_value(text)),
+            HoverEvent::ShowItem(item) =>
+                ("show_item", serde_json::to_value(item)),
+            HoverEvent::ShowEntity(entity) =>
+                ("show_entity", serde_json::to_value(entity)),
+            HoverEvent::Legacy { action, value } => {
+                return RawHoverEvent {
+                    action: action.clone(),
+                    contents: None,
+                    value: Some((**value).clone()),
+                }.serialize(serializer);
+            },
+        };
+        RawHoverEvent {
+            action: action.to_string(),
+            contents: Some(contents.map_err(S::Error::custom)?),
+            value: None,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HoverEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawHoverEvent::deserialize(deserializer)?;
+        let contents = match (raw.contents, raw.value) {
+            (Some(contents), _) => contents,
+            (None, Some(value)) => return Ok(match raw.action.as_str() {
+                "show_text" => HoverEvent::ShowText(Box::new(value)),
+                _ => HoverEvent::Legacy {
+                    action: raw.action,
+                    value: Box::new(value),
+                },
+            }),
+            (None, None) => return Err(de::Error::missing_field("contents")),
+        };
+        match raw.action.as_str() {
+            "show_text" => serde_json::from_value(contents)
+                .map(|text| HoverEvent::ShowText(Box::new(text))),
+            "show_item" => serde_json::from_value(contents)
+                .map(|item| HoverEvent::ShowItem(match item {
+                    RawHoverItem::Id(id) => HoverItem {
+                        id,
+                        count: None,
+                        components: None,
+                        tag: None,
+                        extra: Map::new(),
+                    },
+                    RawHoverItem::Item(item) => item,
+                })),
+            "show_entity" => serde_json::from_value(contents)
+                .map(HoverEvent::ShowEntity),
+            action => return Err(de::Error::unknown_variant(
+                action, &["show_text", "show_item", "show_entity"])),
+        }.map_err(de::Error::custom)
+    }
+}
+
+fn json_to_nbt(value: &Value) -> Option<Tag> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(value) => Tag::from(*value),
+        Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(value), _) => match i32::try_from(value) {
+                Ok(value) => Tag::Int(value),
+                Err(_) => Tag::Long(value),
+            },
+            (None, Some(value)) => Tag::Double(value),
+            (None, None) => return None,
+        },
+        Value::String(value) => Tag::String(value.clone()),
+        Value::Array(array) => Tag::List(
+            array.iter().filter_map(json_to_nbt).collect()),
+        Value::Object(object) => Tag::Compound(object.iter()
+            .filter_map(|(key, value)|
+                Some((key.clone(), json_to_nbt(value)?)))
+            .collect::<Compound>()),
+    })
+}
+
+fn nbt_to_json(tag: &Tag, boolean: bool) -> Value {
+    match tag {
+        Tag::Byte(value) if boolean => Value::Bool(*value != 0),
+        Tag::Byte(value) => Value::from(*value),
+        Tag::Short(value) => Value::from(*value),
+        Tag::Int(value) => Value::from(*value),
+        Tag::Long(value) => Value::from(*value),
+        Tag::Float(value) => Value::from(*value),
+        Tag::Double(value) => Value::from(*value),
+        Tag::ByteArray(array) => Value::from(array.clone()),
+        Tag::String(value) => Value::String(value.clone()),
+        Tag::List(list) => Value::Array(list.iter()
+            .map(|tag| match tag.as_compound() {
+                Some(compound) if compound.len() == 1 =>
+                    nbt_to_json(compound.get("").unwrap_or(tag), false),
+                _ => nbt_to_json(tag, false),
+            })
+            .collect()),
+        Tag::Compound(compound) => Value::Object(compound.iter()
+            .map(|(key, tag)| (
+                key.to_string(),
+                nbt_to_json(tag, BOOLEAN_KEYS.contains(&key)),
+            ))
+            .collect()),
+        Tag::IntArray(array) => Value::from(array.clone()),
+        Tag::LongArray(array) => Value::from(array.clone()),
+    }
+}
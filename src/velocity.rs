@@ -0,0 +1,210 @@
+// Yeahbut October 2026
+
+use std::net::IpAddr;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::mc_types::{self, PacketArray, PacketError, ProtocolError, Result};
+use crate::login::clientbound::{LoginSuccessProperty, PluginRequest};
+use crate::login::serverbound::PluginResponse;
+
+pub const PLAYER_INFO_CHANNEL: &str = "velocity:player_info";
+
+pub const MODERN_DEFAULT: i32 = 1;
+pub const MODERN_WITH_KEY: i32 = 2;
+pub const MODERN_WITH_KEY_V2: i32 = 3;
+pub const MODERN_LAZY_SESSION: i32 = 4;
+pub const MAX_SUPPORTED_VERSION: i32 = MODERN_LAZY_SESSION;
+
+const SIGNATURE_LENGTH: usize = 32;
+
+// 1.19.3 clients no longer send a chat signing key at login
+const KEYLESS_PROTOCOL_VERSION: i32 = 761;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The player's chat signing key, only forwarded in versions 2 and 3.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityPlayerKey {
+    pub expires_at: i64,
+    pub public_key: Vec<u8>,
+    pub key_signature: Vec<u8>,
+    // Only present from version 3
+    pub signer: Option<u128>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityForwarding {
+    pub version: i32,
+    pub client_address: IpAddr,
+    pub uuid: u128,
+    pub username: String,
+    pub properties: Vec<LoginSuccessProperty>,
+    pub key: Option<VelocityPlayerKey>,
+}
+
+impl VelocityForwarding {
+    /// The request a backend sends after Login Start, advertising the
+    /// newest forwarding version it understands.
+    pub fn request(message_id: i32) -> PluginRequest {
+        PluginRequest {
+            message_id,
+            channel: PLAYER_INFO_CHANNEL.to_string(),
+            data: vec![MAX_SUPPORTED_VERSION as u8],
+        }
+    }
+
+    /// The version the proxy should answer a backend's request with.
+    pub fn requested_version(request: &PluginRequest) -> i32 {
+        request.data.first()
+            .map(|version| *version as i32)
+            .unwrap_or(MODERN_DEFAULT)
+    }
+
+    pub fn is_request(request: &PluginRequest) -> bool {
+        request.channel == PLAYER_INFO_CHANNEL
+    }
+
+    /// Picks the version to forward with for a player on `protocol_version`.
+    /// Versions 2 and 3 are only valid for pre-1.19.3 players with a key,
+    /// and a key linked to a signer needs version 3.
+    pub fn negotiate_version(
+        requested: i32,
+        protocol_version: i32,
+        key: Option<&VelocityPlayerKey>,
+    ) -> i32 {
+        if protocol_version >= KEYLESS_PROTOCOL_VERSION {
+            return match requested {
+                MODERN_LAZY_SESSION.. => MODERN_LAZY_SESSION,
+                _ => MODERN_DEFAULT,
+            };
+        }
+        match key {
+            Some(key) if key.signer.is_some() => match requested {
+                MODERN_WITH_KEY_V2.. => MODERN_WITH_KEY_V2,
+                _ => MODERN_DEFAULT,
+            },
+            Some(_) if requested >= MODERN_WITH_KEY => MODERN_WITH_KEY,
+            _ => MODERN_DEFAULT,
+        }
+    }
+
+    pub fn encode(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        let mut data = mc_types::convert_var_int(self.version);
+        data.append(&mut mc_types::convert_string(
+            &self.client_address.to_string()));
+        data.append(&mut mc_types::convert_uuid(self.uuid));
+        data.append(&mut mc_types::convert_string(&self.username));
+        data.append(&mut LoginSuccessProperty::try_convert_array(
+            &self.properties)?);
+        if has_key(self.version) {
+            let key = self.key.as_ref().ok_or(PacketError::InvalidForwarding(
+                "forwarding version requires a player key"))?;
+            if self.version < MODERN_WITH_KEY_V2 && key.signer.is_some() {
+                return Err(PacketError::InvalidForwarding(
+                    "linked player keys require forwarding version 3").into());
+            }
+            data.append(&mut mc_types::convert_i64(key.expires_at));
+            data.append(&mut mc_types::convert_byte_array(&key.public_key));
+            data.append(&mut mc_types::convert_byte_array(
+                &key.key_signature));
+            if self.version >= MODERN_WITH_KEY_V2 {
+                data.append(&mut mc_types::convert_bool(
+                    key.signer.is_some()));
+                if let Some(signer) = key.signer {
+                    data.append(&mut mc_types::convert_uuid(signer));
+                }
+            }
+        }
+
+        let mut mac = HmacSha256::new_from_slice(secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(&data);
+        let mut out = mac.finalize().into_bytes().to_vec();
+        out.append(&mut data);
+        Ok(out)
+    }
+
+    /// Checks the signature with the shared secret before decoding.
+    pub fn verify(data: &[u8], secret: &[u8]) -> Result<Self> {
+        if data.len() < SIGNATURE_LENGTH {
+            return Err(PacketError::RanOutOfBytes("Signature").into());
+        }
+        let (signature, mut data) = data.split_at(SIGNATURE_LENGTH);
+        let mut mac = HmacSha256::new_from_slice(secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac.verify_slice(signature).map_err(|_|
+            ProtocolError::Authentication(
+                "invalid forwarding signature".to_string()))?;
+
+        let version = mc_types::get_var_int(&mut data)?;
+        if !(MODERN_DEFAULT..=MAX_SUPPORTED_VERSION).contains(&version) {
+            return Err(PacketError::InvalidForwarding(
+                "unsupported forwarding version").into());
+        }
+        let client_address = mc_types::get_string(&mut data)?
+            .parse()
+            .map_err(|_| PacketError::InvalidForwarding(
+                "invalid client address"))?;
+        let uuid = mc_types::get_uuid(&mut data)?;
+        let username = mc_types::get_string_limited(
+            &mut data, mc_types::MAX_USERNAME_LENGTH)?;
+        let properties = LoginSuccessProperty::get_array(&mut data)?;
+        let key = match has_key(version) {
+            true => Some(VelocityPlayerKey {
+                expires_at: mc_types::get_i64(&mut data)?,
+                public_key: mc_types::get_byte_array(&mut data)?,
+                key_signature: mc_types::get_byte_array(&mut data)?,
+                signer: match version >= MODERN_WITH_KEY_V2 &&
+                    mc_types::get_bool(&mut data)?
+                {
+                    true => Some(mc_types::get_uuid(&mut data)?),
+                    false => None,
+                },
+            }),
+            false => None,
+        };
+        if !data.is_empty() {
+            return Err(PacketError::InvalidForwarding(
+                "trailing data after player info").into());
+        }
+        Ok(Self {
+            version,
+            client_address,
+            uuid,
+            username,
+            properties,
+            key,
+        })
+    }
+
+    /// The proxy's answer to a backend's player info request.
+    pub fn response(&self, message_id: i32, secret: &[u8])
+        -> Result<PluginResponse>
+    {
+        Ok(PluginResponse {
+            message_id,
+            successful: true,
+            data: self.encode(secret)?,
+        })
+    }
+
+    /// Fails if the proxy did not understand the request, which means the
+    /// player connected without going through a Velocity proxy.
+    pub fn from_response(
+        response: &PluginResponse,
+        secret: &[u8],
+    ) -> Result<Self> {
+        if !response.successful {
+            return Err(PacketError::InvalidForwarding(
+                "proxy did not forward player info").into());
+        }
+        Self::verify(&response.data, secret)
+    }
+}
+
+fn has_key(version: i32) -> bool {
+    (MODERN_WITH_KEY..MODERN_LAZY_SESSION).contains(&version)
+}
@@ -0,0 +1,80 @@
+// Yeahbut October 2026
+
+use std::fmt;
+
+use crate::mc_types::{ProtocolError, Result};
+
+/// Supported versions run from 1.20 (763) to 1.21.1 (767). Newer
+/// versions are rejected as unsupported rather than guessed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    V1_20_1 = 763,
+    V1_20_2 = 764,
+    V1_20_3 = 765,
+    V1_20_5 = 766,
+    V1_21 = 767,
+}
+
+pub const SUPPORTED_VERSIONS: &[ProtocolVersion] = &[
+    ProtocolVersion::V1_20_1,
+    ProtocolVersion::V1_20_2,
+    ProtocolVersion::V1_20_3,
+    ProtocolVersion::V1_20_5,
+    ProtocolVersion::V1_21,
+];
+
+pub const VERSION_COUNT: usize = SUPPORTED_VERSIONS.len();
+
+impl ProtocolVersion {
+    pub const OLDEST: Self = Self::V1_20_1;
+    pub const LATEST: Self = Self::V1_21;
+
+    pub fn from_protocol(protocol: i32) -> Result<Self> {
+        SUPPORTED_VERSIONS.iter()
+            .find(|version| version.protocol() == protocol)
+            .copied()
+            .ok_or(ProtocolError::UnsupportedVersion(protocol))
+    }
+
+    pub const fn protocol(self) -> i32 {
+        self as i32
+    }
+
+    /// The position of this version in `SUPPORTED_VERSIONS`.
+    pub const fn index(self) -> usize {
+        let mut index = 0;
+        while SUPPORTED_VERSIONS[index].protocol() != self.protocol() {
+            index += 1;
+        }
+        index
+    }
+
+    pub const fn names(self) -> &'static [&'static str] {
+        match self {
+            Self::V1_20_1 => &["1.20", "1.20.1"],
+            Self::V1_20_2 => &["1.20.2"],
+            Self::V1_20_3 => &["1.20.3", "1.20.4"],
+            Self::V1_20_5 => &["1.20.5", "1.20.6"],
+            Self::V1_21 => &["1.21", "1.21.1"],
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        let names = self.names();
+        names[names.len() - 1]
+    }
+}
+
+impl TryFrom<i32> for ProtocolVersion {
+    type Error = ProtocolError;
+
+    fn try_from(protocol: i32) -> Result<Self> {
+        Self::from_protocol(protocol)
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.protocol())
+    }
+}
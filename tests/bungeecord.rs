@@ -0,0 +1,89 @@
+use purple_cello_mc_protocol::bungeecord::BungeeCordForwarding;
+use purple_cello_mc_protocol::handshake::serverbound::Handshake;
+use purple_cello_mc_protocol::login::clientbound::LoginSuccessProperty;
+use purple_cello_mc_protocol::mc_types::{self, Packet, PacketError,
+    ProtocolError};
+
+const UUID: u128 = 0x069a79f444e94726a5befca90e38aaf5;
+
+fn textures() -> LoginSuccessProperty {
+    // Sized like a real Mojang skin property
+    LoginSuccessProperty {
+        name: "textures".to_string(),
+        value: "e".repeat(600),
+        signature: Some("s".repeat(684)),
+    }
+}
+
+fn handshake(server_address: String) -> Handshake {
+    Handshake {
+        protocol_version: mc_types::VERSION_PROTOCOL,
+        server_address,
+        server_port: 25565,
+        next_state: 2,
+    }
+}
+
+#[test]
+fn forwarding_without_properties_round_trips() {
+    let forwarding = BungeeCordForwarding::new(
+        "mc.example.com", "203.0.113.7".parse().unwrap(), UUID);
+    let address = forwarding.to_string();
+    assert_eq!(address, concat!("mc.example.com\x00203.0.113.7\0",
+        "069a79f444e94726a5befca90e38aaf5"));
+    assert_eq!(BungeeCordForwarding::parse(&address).unwrap(), forwarding);
+}
+
+#[test]
+fn forwarding_with_properties_round_trips() {
+    let forwarding = BungeeCordForwarding::new(
+        "mc.example.com", "2001:db8::1".parse().unwrap(), UUID)
+        .with_properties(vec![textures()]);
+    let address = forwarding.to_string();
+    assert_eq!(BungeeCordForwarding::parse(&address).unwrap(), forwarding);
+    assert_eq!(BungeeCordForwarding::parse(&address).unwrap().to_string(),
+        address);
+}
+
+#[test]
+fn forwarding_fits_in_handshake() {
+    let forwarding = BungeeCordForwarding::new(
+        "a".repeat(255), "2001:db8::1".parse().unwrap(), UUID)
+        .with_properties(vec![textures(), textures()]);
+    let mut packet = handshake(String::new());
+    forwarding.apply(&mut packet);
+    let data = packet.convert().unwrap();
+    let mut cursor = &data[1..];
+    let read = Handshake::get(&mut cursor).unwrap();
+    assert_eq!(BungeeCordForwarding::from_handshake(&read).unwrap(),
+        forwarding);
+}
+
+#[test]
+fn oversized_server_address_is_rejected() {
+    let data = handshake("a".repeat(8193)).convert().unwrap();
+    assert!(matches!(Handshake::get(&mut &data[1..]),
+        Err(ProtocolError::Decode(PacketError::LengthExceeded { .. }, _))));
+}
+
+#[test]
+fn bad_client_address_is_rejected() {
+    let address = "host\0not-an-ip\x00069a79f444e94726a5befca90e38aaf5";
+    assert!(matches!(BungeeCordForwarding::parse(address),
+        Err(ProtocolError::Decode(PacketError::InvalidForwarding(_), _))));
+}
+
+#[test]
+fn bad_uuid_is_rejected() {
+    for uuid in ["069a79f4", "zz9a79f444e94726a5befca90e38aaf5"] {
+        let address = format!("host\x00203.0.113.7\0{}", uuid);
+        assert!(matches!(BungeeCordForwarding::parse(&address),
+            Err(ProtocolError::Decode(PacketError::InvalidUUIDString, _))));
+    }
+}
+
+#[test]
+fn missing_fields_are_rejected() {
+    assert!(BungeeCordForwarding::parse("mc.example.com").is_err());
+    assert!(BungeeCordForwarding::parse("host\x00203.0.113.7").is_err());
+}
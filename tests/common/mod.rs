@@ -0,0 +1,20 @@
+#![allow(dead_code)]
+
+use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+
+use purple_cello_mc_protocol::mc_types::ProtocolConnection;
+
+pub type Connection = ProtocolConnection<
+    ReadHalf<DuplexStream>,
+    WriteHalf<DuplexStream>,
+>;
+
+pub fn connection(stream: DuplexStream) -> Connection {
+    let (stream_read, stream_write) = tokio::io::split(stream);
+    ProtocolConnection::new(stream_read, stream_write)
+}
+
+pub fn connections() -> (Connection, Connection) {
+    let (client, server) = tokio::io::duplex(65536);
+    (connection(client), connection(server))
+}
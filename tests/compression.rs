@@ -0,0 +1,106 @@
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use purple_cello_mc_protocol::mc_types::{
+    self, ProtocolError, ProtocolRead, ProtocolWrite};
+
+mod common;
+
+use common::Connection;
+
+fn connections(threshold: i32) -> (Connection, Connection) {
+    let (mut client, mut server) = common::connections();
+    client.set_compression(threshold);
+    server.set_compression(threshold);
+    (client, server)
+}
+
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+async fn write_frame(conn: &mut Connection, data_length: i32, body: &[u8]) {
+    let mut frame = mc_types::convert_var_int(data_length);
+    frame.extend_from_slice(body);
+    let mut out = mc_types::convert_var_int(frame.len() as i32);
+    out.append(&mut frame);
+    conn.stream_write.write_all(&out).await.unwrap();
+}
+
+async fn read_raw(conn: &mut Connection, length: usize) -> Vec<u8> {
+    let mut buffer = vec![0; length];
+    conn.stream_read.read_exact(&mut buffer).await.unwrap();
+    buffer
+}
+
+async fn read_length(conn: &mut Connection) -> usize {
+    let mut bytes = vec![];
+    loop {
+        let byte = conn.stream_read.read_u8().await.unwrap();
+        bytes.push(byte);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    let length = mc_types::get_var_int(&mut bytes.as_slice()).unwrap();
+    length as usize
+}
+
+fn is_compression_error<T: std::fmt::Debug>(
+    result: mc_types::Result<T>,
+) -> bool {
+    matches!(result, Err(ProtocolError::Compression(_)))
+}
+
+#[tokio::test]
+async fn packets_below_threshold_are_sent_uncompressed() {
+    let (mut client, mut server) = connections(256);
+    client.write_data(&mut vec![0x01, 0x02, 0x03]).await.unwrap();
+    assert_eq!(read_raw(&mut server, 5).await, [4, 0, 1, 2, 3]);
+
+    write_frame(&mut client, 0, &[0x01, 0x02, 0x03]).await;
+    assert_eq!(server.read_data().await.unwrap(), [1, 2, 3]);
+}
+
+#[tokio::test]
+async fn packets_above_threshold_round_trip_through_zlib() {
+    let (mut client, mut server) = connections(256);
+    let data: Vec<u8> = (0..1024).map(|i| (i % 7) as u8).collect();
+    client.write_data(&mut data.clone()).await.unwrap();
+    assert_eq!(server.read_data().await.unwrap(), data);
+
+    // The declared data length precedes the zlib stream
+    client.write_data(&mut data.clone()).await.unwrap();
+    let length = read_length(&mut server).await;
+    let frame = read_raw(&mut server, length).await;
+    assert_eq!(&frame[..2], mc_types::convert_var_int(1024).as_slice());
+    assert_eq!(&frame[2..], zlib(&data).as_slice());
+}
+
+#[tokio::test]
+async fn inconsistent_data_length_is_rejected() {
+    let (mut client, mut server) = connections(256);
+    let data = vec![0x2A; 512];
+    write_frame(&mut client, 511, &zlib(&data)).await;
+    assert!(is_compression_error(server.read_data().await));
+
+    write_frame(&mut client, 513, &zlib(&data)).await;
+    assert!(is_compression_error(server.read_data().await));
+}
+
+#[tokio::test]
+async fn data_length_outside_the_limits_is_rejected() {
+    let (mut client, mut server) = connections(256);
+    let data = vec![0x2A; 255];
+    write_frame(&mut client, 255, &zlib(&data)).await;
+    assert!(is_compression_error(server.read_data().await));
+
+    let length = mc_types::MAX_UNCOMPRESSED_LENGTH as i32 + 1;
+    write_frame(&mut client, length, &zlib(&[0; 16])).await;
+    assert!(is_compression_error(server.read_data().await));
+}
@@ -0,0 +1,91 @@
+use purple_cello_mc_protocol::configuration::{clientbound, serverbound};
+use purple_cello_mc_protocol::mc_types::Packet;
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+mod common;
+
+#[tokio::test]
+async fn configuration_finishes_after_known_packs_and_registries() {
+    let version = ProtocolVersion::V1_21;
+    let (mut client, mut server) = common::connections();
+    clientbound::KnownPacks {
+        known_packs: vec![clientbound::KnownPack {
+            namespace: "minecraft".to_string(),
+            id: "core".to_string(),
+            version: "1.21".to_string(),
+        }],
+    }.write_versioned(&mut server, version).await.unwrap();
+    clientbound::RegistryData {
+        registry_id: "minecraft:dimension_type".to_string(),
+        entries: vec![0x01, 0x02],
+    }.write_versioned(&mut server, version).await.unwrap();
+    clientbound::FinishConfiguration {}
+        .write_versioned(&mut server, version).await.unwrap();
+
+    match clientbound::Configuration::read_versioned(&mut client, version)
+        .await.unwrap()
+    {
+        clientbound::Configuration::KnownPacks(packs) => {
+            assert_eq!(packs.known_packs.len(), 1);
+            assert_eq!(packs.known_packs[0].id, "core");
+        },
+        _ => panic!("expected known packs"),
+    }
+    match clientbound::Configuration::read_versioned(&mut client, version)
+        .await.unwrap()
+    {
+        clientbound::Configuration::RegistryData(registry) => {
+            assert_eq!(registry.registry_id, "minecraft:dimension_type");
+            assert_eq!(registry.entries, [0x01, 0x02]);
+        },
+        _ => panic!("expected registry data"),
+    }
+    assert!(matches!(
+        clientbound::Configuration::read_versioned(&mut client, version)
+            .await.unwrap(),
+        clientbound::Configuration::FinishConfiguration(_)));
+
+    serverbound::AcknowledgeFinishConfiguration {}
+        .write_versioned(&mut client, version).await.unwrap();
+    assert!(matches!(
+        serverbound::Configuration::read_versioned(&mut server, version)
+            .await.unwrap(),
+        serverbound::Configuration::AcknowledgeFinishConfiguration(_)));
+}
+
+#[test]
+fn resource_pack_push_follows_version() {
+    let push = clientbound::ResourcePackPush {
+        uuid: 7,
+        url: "https://example.com/pack.zip".to_string(),
+        hash: "0".repeat(40),
+        forced: true,
+        prompt: None,
+    };
+    let old = push.convert_versioned(ProtocolVersion::V1_20_2).unwrap();
+    let new = push.convert_versioned(ProtocolVersion::V1_21).unwrap();
+    assert_eq!(old[0], 0x06);
+    assert_eq!(new[0], 0x09);
+    // The pack uuid was added in 1.20.3
+    assert_eq!(new.len(), old.len() + 16);
+
+    let mut data = &old[1..];
+    let read = clientbound::ResourcePackPush::get_versioned(
+        &mut data, ProtocolVersion::V1_20_2).unwrap();
+    assert_eq!(read.uuid, 0);
+    assert_eq!(read.url, push.url);
+    let mut data = &new[1..];
+    let read = clientbound::ResourcePackPush::get_versioned(
+        &mut data, ProtocolVersion::V1_21).unwrap();
+    assert_eq!(read.uuid, 7);
+}
+
+#[test]
+fn configuration_is_unavailable_before_1_20_2() {
+    assert!(clientbound::Configuration::packet_ids_for(
+        ProtocolVersion::V1_20_1).is_empty());
+    assert!(clientbound::FinishConfiguration {}
+        .convert_versioned(ProtocolVersion::V1_20_1).is_err());
+    assert!(serverbound::Configuration::get_versioned(
+        0x03, &mut [].as_slice(), ProtocolVersion::V1_20_1).is_err());
+}
@@ -0,0 +1,73 @@
+use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+
+use purple_cello_mc_protocol::mc_types::{
+    Packet, ProtocolConnection, ProtocolRead, ProtocolWrite,
+    ReadHaftProtocolConnection, WriteHaftProtocolConnection};
+use purple_cello_mc_protocol::status::{clientbound, serverbound};
+
+mod common;
+
+const _: fn() = || {
+    fn assert_spawnable<T: Send + 'static>() {}
+    assert_spawnable::<ProtocolConnection>();
+    assert_spawnable::<ReadHaftProtocolConnection>();
+    assert_spawnable::<WriteHaftProtocolConnection>();
+    assert_spawnable::<common::Connection>();
+    assert_spawnable::<ReadHaftProtocolConnection<ReadHalf<DuplexStream>>>();
+    assert_spawnable::<
+        WriteHaftProtocolConnection<WriteHalf<DuplexStream>>>();
+};
+
+// Each direction over its own pipe rather than halves of one stream
+fn piped() -> (
+    ProtocolConnection<DuplexStream, DuplexStream>,
+    ProtocolConnection<DuplexStream, DuplexStream>,
+) {
+    let (client_write, server_read) = tokio::io::duplex(1024);
+    let (server_write, client_read) = tokio::io::duplex(1024);
+    (
+        ProtocolConnection::new(client_read, client_write),
+        ProtocolConnection::new(server_read, server_write),
+    )
+}
+
+#[tokio::test]
+async fn any_transport_carries_frames() {
+    let (mut client, mut server) = piped();
+    client.write_data(&mut vec![0x01, 0x02, 0x03]).await.unwrap();
+    assert_eq!(server.read_data().await.unwrap(), [1, 2, 3]);
+
+    server.set_compression(4);
+    client.set_compression(4);
+    let data: Vec<u8> = (0..64).collect();
+    server.write_data(&mut data.clone()).await.unwrap();
+    assert_eq!(client.read_data().await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn packets_round_trip_over_any_transport() {
+    let (mut client, mut server) = piped();
+    serverbound::Ping { payload: 42 }.write(&mut client).await.unwrap();
+    match serverbound::StatusPackets::read(&mut server).await.unwrap() {
+        serverbound::StatusPackets::Ping(ping) =>
+            assert_eq!(ping.payload, 42),
+        _ => panic!("expected ping"),
+    }
+    clientbound::Ping { payload: 42 }.write(&mut server).await.unwrap();
+    assert!(matches!(
+        clientbound::StatusPackets::read(&mut client).await.unwrap(),
+        clientbound::StatusPackets::Ping(_)));
+}
+
+#[tokio::test]
+async fn connections_move_into_spawned_tasks() {
+    let (client, server) = common::connections();
+    let (_write, mut read) = server.split_conn().unwrap();
+    let reader = tokio::spawn(async move { read.read_data().await });
+    let writer = tokio::spawn(async move {
+        let mut client = client;
+        client.write_data(&mut vec![0x07]).await
+    });
+    writer.await.unwrap().unwrap();
+    assert_eq!(reader.await.unwrap().unwrap(), [0x07]);
+}
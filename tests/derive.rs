@@ -0,0 +1,100 @@
+use purple_cello_mc_protocol::mc_types::{self, Packet, PacketArray};
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+#[derive(Debug, Clone, PartialEq, PacketArray)]
+pub struct Entry {
+    pub name: String,
+    #[packet(var_int)]
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Packet)]
+#[packet(id = 0x10)]
+pub struct Sample {
+    #[packet(var_int)]
+    pub count: i32,
+    #[packet(var_long)]
+    pub time: i64,
+    pub nickname: Option<String>,
+    #[packet(var_int)]
+    pub slot: Option<i32>,
+    pub entries: Vec<Entry>,
+    #[packet(since = V1_20_5)]
+    pub added: i32,
+    #[packet(until = V1_20_2)]
+    pub removed: bool,
+    #[packet(remaining)]
+    pub rest: Vec<u8>,
+}
+
+fn sample() -> Sample {
+    Sample {
+        count: 300,
+        time: 1 << 40,
+        nickname: Some("Steve".to_string()),
+        slot: None,
+        entries: vec![Entry { name: "a".to_string(), value: 1 }],
+        added: 7,
+        removed: true,
+        rest: vec![0xCA, 0xFE],
+    }
+}
+
+fn round_trip(packet: &Sample, version: ProtocolVersion) -> Sample {
+    let data = packet.convert_versioned(version).unwrap();
+    let mut cursor = data.as_slice();
+    assert_eq!(mc_types::get_var_int(&mut cursor).unwrap(), 0x10);
+    let read = Sample::get_versioned(&mut cursor, version).unwrap();
+    assert!(cursor.is_empty());
+    read
+}
+
+#[test]
+fn fields_use_their_declared_encoding() {
+    let data = sample().convert_versioned(ProtocolVersion::V1_21).unwrap();
+    let mut expected = vec![0x10];
+    expected.append(&mut mc_types::convert_var_int(300));
+    expected.append(&mut mc_types::convert_var_long(1 << 40));
+    expected.append(&mut mc_types::convert_bool(true));
+    expected.append(&mut mc_types::convert_string("Steve"));
+    expected.append(&mut mc_types::convert_bool(false));
+    expected.append(&mut mc_types::convert_var_int(1));
+    expected.append(&mut mc_types::convert_string("a"));
+    expected.append(&mut mc_types::convert_var_int(1));
+    expected.append(&mut mc_types::convert_i32(7));
+    expected.extend_from_slice(&[0xCA, 0xFE]);
+    assert_eq!(data, expected);
+}
+
+#[test]
+fn optional_and_var_int_fields_round_trip() {
+    let mut packet = sample();
+    assert_eq!(round_trip(&packet, ProtocolVersion::V1_21),
+        Sample { removed: false, ..sample() });
+    packet.nickname = None;
+    packet.slot = Some(5);
+    packet.time = i64::MAX;
+    packet.rest = vec![];
+    assert_eq!(round_trip(&packet, ProtocolVersion::V1_21),
+        Sample { removed: false, ..packet });
+}
+
+#[test]
+fn versioned_fields_default_outside_their_range() {
+    assert_eq!(round_trip(&sample(), ProtocolVersion::V1_20_1),
+        Sample { added: 0, ..sample() });
+    assert_eq!(round_trip(&sample(), ProtocolVersion::V1_20_3),
+        Sample { added: 0, removed: false, ..sample() });
+    assert_eq!(round_trip(&sample(), ProtocolVersion::V1_20_5),
+        Sample { removed: false, ..sample() });
+}
+
+#[test]
+fn arrays_of_derived_structs_round_trip() {
+    let entries = vec![
+        Entry { name: "a".to_string(), value: 1 },
+        Entry { name: "b".to_string(), value: 300 },
+    ];
+    let data = Entry::try_convert_array(&entries).unwrap();
+    assert_eq!(Entry::get_array(&mut data.as_slice()).unwrap(), entries);
+}
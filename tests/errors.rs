@@ -0,0 +1,118 @@
+use tokio::io::AsyncWriteExt;
+
+use purple_cello_mc_protocol::handshake::serverbound::{
+    Handshake, HandshakeEnum};
+use purple_cello_mc_protocol::mc_types::{
+    self, ConnectionState, Direction, Packet, PacketArray, PacketContext,
+    PacketError, ProtocolError, ProtocolRead, ProtocolWrite};
+
+mod common;
+
+use common::connections;
+
+const _: fn() = || {
+    fn assert_error<T: std::error::Error + Send + Sync + 'static>() {}
+    assert_error::<ProtocolError>();
+    assert_error::<PacketError>();
+};
+
+fn length_exceeded<T: std::fmt::Debug>(
+    result: mc_types::Result<T>,
+) -> (&'static str, usize, usize) {
+    match result {
+        Err(ProtocolError::Decode(
+            PacketError::LengthExceeded { field, length, max }, _)) =>
+            (field, length, max),
+        other => panic!("expected LengthExceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn strings_past_their_limit_are_rejected() {
+    let data = mc_types::convert_string("ABCDEFGHIJKLMNOPQ");
+    assert_eq!(length_exceeded(mc_types::get_string_limited(
+        &mut data.as_slice(), mc_types::MAX_USERNAME_LENGTH)),
+        ("String", 17, 16));
+    // The byte length is checked before decoding
+    let data = mc_types::convert_string(&"a".repeat(49));
+    assert_eq!(length_exceeded(mc_types::get_string_limited(
+        &mut data.as_slice(), 16)), ("String", 49, 48));
+    // Characters count as UTF-16 code units
+    let data = mc_types::convert_string(&"\u{1F600}".repeat(8));
+    assert_eq!(mc_types::get_string_limited(&mut data.as_slice(), 16)
+        .unwrap().chars().count(), 8);
+}
+
+#[test]
+fn byte_and_element_arrays_past_their_limit_are_rejected() {
+    let data = mc_types::convert_byte_array(&[0; 10]);
+    assert_eq!(length_exceeded(mc_types::get_byte_array_limited(
+        &mut data.as_slice(), 9)), ("Byte Array", 10, 9));
+
+    let data = mc_types::convert_var_int_array(&[1, 2, 3]);
+    assert_eq!(length_exceeded(mc_types::get_var_int_array_limited(
+        &mut data.as_slice(), 2)), ("Array", 3, 2));
+
+    let data = String::try_convert_array(
+        &["a".to_string(), "b".to_string()]).unwrap();
+    assert_eq!(length_exceeded(String::get_array_limited(
+        &mut data.as_slice(), 1)), ("Array", 2, 1));
+    // A declared length longer than the data is rejected up front
+    assert_eq!(length_exceeded(String::get_array(
+        &mut [0x7F, 0x00].as_slice())).0, "Array");
+}
+
+#[tokio::test]
+async fn oversized_frames_are_framing_errors() {
+    let (mut client, mut server) = connections();
+    server.set_max_packet_length(8);
+    client.stream_write.write_all(&mc_types::convert_var_int(9))
+        .await.unwrap();
+    match server.read_data().await {
+        Err(ProtocolError::Framing(PacketError::LengthExceeded {
+            field, length, max,
+        })) => assert_eq!((field, length, max), ("Packet", 9, 8)),
+        other => panic!("expected a framing error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn decode_errors_carry_packet_context() {
+    let (mut client, mut server) = connections();
+    let handshake = Handshake {
+        protocol_version: mc_types::VERSION_PROTOCOL,
+        server_address: "a".repeat(8193),
+        server_port: 25565,
+        next_state: 2,
+    };
+    client.write_data(&mut handshake.convert().unwrap()).await.unwrap();
+    let error = match HandshakeEnum::read(&mut server).await {
+        Err(error) => error,
+        Ok(_) => panic!("expected the address to be too long"),
+    };
+    assert_eq!(error.context(), Some(&PacketContext {
+        state: Some(ConnectionState::Handshake),
+        direction: Some(Direction::Serverbound),
+        packet_id: Some(0),
+        field: Some("server_address"),
+    }));
+    assert_eq!(error.to_string(), concat!(
+        "Decode error in Handshake Serverbound packet 0x00 field ",
+        "server_address: String length 8193 exceeds maximum of 8192"));
+}
+
+#[test]
+fn context_is_only_filled_once() {
+    let error = ProtocolError::from(PacketError::InvalidUUIDString)
+        .with_field("uuid")
+        .with_field("outer")
+        .with_packet_id(2)
+        .with_packet_id(3);
+    let context = error.context().unwrap();
+    assert_eq!(context.field, Some("uuid"));
+    assert_eq!(context.packet_id, Some(2));
+    assert!(ProtocolError::UnsupportedVersion(1).with_field("x")
+        .context().is_none());
+    assert!(ProtocolError::UnsupportedVersion(1).to_string()
+        .contains("1.21.1 (767)"));
+}
@@ -0,0 +1,64 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use purple_cello_mc_protocol::favicon;
+use purple_cello_mc_protocol::mc_types::{PacketError, ProtocolError};
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut out = vec![];
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&vec![0x40; (width * height * 3) as usize])
+        .unwrap();
+    writer.finish().unwrap();
+    out
+}
+
+fn is_invalid_favicon<T>(result: Result<T, ProtocolError>) -> bool {
+    matches!(result,
+        Err(ProtocolError::Decode(PacketError::InvalidFavicon(_), _)))
+}
+
+#[test]
+fn square_64_png_passes_through_unchanged() {
+    let image = png(64, 64);
+    let icon = favicon::from_png(&image, false).unwrap();
+    assert_eq!(icon, format!("data:image/png;base64,{}",
+        STANDARD.encode(&image)));
+    assert_eq!(favicon::to_png(&icon).unwrap(), image);
+    assert_eq!(favicon::from_png(&image, true).unwrap(), icon);
+}
+
+#[test]
+fn other_sizes_need_rescale() {
+    let image = png(128, 96);
+    assert!(is_invalid_favicon(favicon::from_png(&image, false)));
+    let icon = favicon::from_png(&image, true).unwrap();
+    let rescaled = favicon::to_png(&icon).unwrap();
+    assert_eq!(favicon::png_dimensions(&rescaled).unwrap(), (64, 64));
+}
+
+#[test]
+fn to_png_accepts_line_wrapped_base64() {
+    let image = png(64, 64);
+    let encoded = STANDARD.encode(&image);
+    let wrapped: Vec<String> = encoded.as_bytes()
+        .chunks(76)
+        .map(|line| String::from_utf8(line.to_vec()).unwrap())
+        .collect();
+    let icon = format!("data:image/png;base64,{}", wrapped.join("\r\n"));
+    assert_eq!(favicon::to_png(&icon).unwrap(), image);
+}
+
+#[test]
+fn bad_prefix_or_signature_is_rejected() {
+    let encoded = STANDARD.encode(png(64, 64));
+    assert!(is_invalid_favicon(favicon::to_png(
+        &format!("data:image/jpeg;base64,{}", encoded))));
+    assert!(is_invalid_favicon(favicon::to_png(&encoded)));
+    assert!(is_invalid_favicon(favicon::to_png(&format!(
+        "data:image/png;base64,{}", STANDARD.encode(b"GIF89a....")))));
+    assert!(is_invalid_favicon(favicon::to_png("data:image/png;base64,!")));
+    assert!(is_invalid_favicon(favicon::png_dimensions(b"\x89PNG")));
+}
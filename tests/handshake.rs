@@ -0,0 +1,60 @@
+use purple_cello_mc_protocol::handshake::serverbound::{
+    ForgeMarker, ServerAddress};
+
+fn round_trip(address: &str) -> ServerAddress {
+    let parsed = ServerAddress::parse(address);
+    assert_eq!(parsed.to_string(), address);
+    parsed
+}
+
+#[test]
+fn plain_hostname_round_trips() {
+    let address = round_trip("mc.example.com");
+    assert_eq!(address.hostname, "mc.example.com");
+    assert!(!address.trailing_dot);
+    assert_eq!(address.forge, None);
+    assert_eq!(address.suffix, "");
+}
+
+#[test]
+fn trailing_dot_round_trips() {
+    let address = round_trip("mc.example.com.");
+    assert_eq!(address.hostname, "mc.example.com");
+    assert!(address.trailing_dot);
+}
+
+#[test]
+fn forge_markers_round_trip() {
+    for (raw, forge) in [
+        ("mc.example.com\0FML\0", ForgeMarker::Fml),
+        ("mc.example.com\0FML2\0", ForgeMarker::Fml2),
+        ("mc.example.com\0FML3\0", ForgeMarker::Fml3),
+    ] {
+        let address = round_trip(raw);
+        assert_eq!(address.hostname, "mc.example.com");
+        assert_eq!(address.forge, Some(forge));
+        assert_eq!(address.suffix, "");
+    }
+    let address = round_trip("mc.example.com.\0FML2\0");
+    assert!(address.trailing_dot);
+    assert_eq!(address.forge, Some(ForgeMarker::Fml2));
+}
+
+#[test]
+fn bungeecord_suffix_round_trips() {
+    let suffix = concat!("\x00203.0.113.7\x00",
+        "069a79f444e94726a5befca90e38aaf5\x00",
+        r#"[{"name":"textures","value":"e30="}]"#);
+    let raw = format!("mc.example.com{}", suffix);
+    let address = round_trip(&raw);
+    assert_eq!(address.hostname, "mc.example.com");
+    assert_eq!(address.forge, None);
+    assert_eq!(address.suffix, suffix);
+}
+
+#[test]
+fn unknown_suffix_is_not_a_forge_marker() {
+    let address = round_trip("mc.example.com\0FML4\0");
+    assert_eq!(address.forge, None);
+    assert_eq!(address.suffix, "\0FML4\0");
+}
@@ -0,0 +1,100 @@
+use tokio::io::AsyncWriteExt;
+
+use purple_cello_mc_protocol::handshake::serverbound::{
+    Handshake, HandshakeStart};
+use purple_cello_mc_protocol::legacy_ping::{
+    LegacyPing, LegacyPingHost, PING_HOST_CHANNEL};
+use purple_cello_mc_protocol::mc_types::{
+    self, Packet, PacketError, ProtocolError};
+
+mod common;
+
+async fn read_start(bytes: &[u8]) -> HandshakeStart {
+    let (mut client, server) = tokio::io::duplex(4096);
+    let mut conn = common::connection(server);
+    client.write_all(bytes).await.unwrap();
+    // The client stays open so lone pings are detected by the timeout
+    let start = conn.read_handshake().await.unwrap();
+    drop(client);
+    start
+}
+
+fn framed_handshake(address_length: usize) -> (Handshake, Vec<u8>) {
+    let handshake = Handshake {
+        protocol_version: mc_types::VERSION_PROTOCOL,
+        server_address: "a".repeat(address_length),
+        server_port: 25565,
+        next_state: 2,
+    };
+    let mut data = handshake.convert().unwrap();
+    let mut framed = mc_types::convert_var_int(data.len() as i32);
+    framed.append(&mut data);
+    (handshake, framed)
+}
+
+async fn assert_legacy_ping(ping: LegacyPing) {
+    match read_start(&ping.convert()).await {
+        HandshakeStart::LegacyPing(read) => assert_eq!(read, ping),
+        HandshakeStart::Handshake(_) => panic!("read a modern handshake"),
+    }
+}
+
+async fn assert_handshake(length: usize, address_length: usize) {
+    let (handshake, framed) = framed_handshake(address_length);
+    assert_eq!(framed.len() - 2, length);
+    match read_start(&framed).await {
+        HandshakeStart::Handshake(read) => {
+            assert_eq!(read.server_address, handshake.server_address);
+            assert_eq!(read.next_state, handshake.next_state);
+        },
+        HandshakeStart::LegacyPing(_) => panic!("read a legacy ping"),
+    }
+}
+
+#[tokio::test]
+async fn beta_ping_is_detected() {
+    assert_legacy_ping(LegacyPing::Beta).await;
+}
+
+#[tokio::test]
+async fn v1_4_ping_is_detected() {
+    assert_legacy_ping(LegacyPing::V1_4).await;
+}
+
+#[tokio::test]
+async fn v1_6_ping_is_detected() {
+    assert_legacy_ping(LegacyPing::V1_6(LegacyPingHost {
+        protocol_version: 78,
+        hostname: "localhost".to_string(),
+        port: 25565,
+    })).await;
+}
+
+#[tokio::test]
+async fn handshake_of_length_250_is_not_a_ping() {
+    // Starts with 0xFA 0x01
+    assert_handshake(250, 242).await;
+}
+
+#[tokio::test]
+async fn handshake_of_length_254_is_not_a_ping() {
+    // Starts with 0xFE 0x01, like a 1.4 ping
+    assert_handshake(254, 246).await;
+}
+
+#[tokio::test]
+async fn malformed_v1_6_ping_is_an_error() {
+    let mut data = LegacyPing::V1_6(LegacyPingHost {
+        protocol_version: 78,
+        hostname: "localhost".to_string(),
+        port: 25565,
+    }).convert();
+    // Truncate the MC|PingHost payload to its protocol version
+    data.truncate(3 + 2 + PING_HOST_CHANNEL.len() * 2);
+    data.extend_from_slice(&[0x00, 0x01, 78]);
+    let (mut client, server) = tokio::io::duplex(4096);
+    let mut conn = common::connection(server);
+    client.write_all(&data).await.unwrap();
+    assert!(matches!(conn.read_handshake().await,
+        Err(ProtocolError::Decode(PacketError::InvalidLegacyPing(_), _))));
+}
@@ -0,0 +1,56 @@
+use purple_cello_mc_protocol::login::serverbound::LoginStart;
+use purple_cello_mc_protocol::mc_types::{self, Packet};
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+const UUID: u128 = 0x069a79f444e94726a5befca90e38aaf5;
+
+fn round_trip(packet: &LoginStart, version: ProtocolVersion)
+    -> (LoginStart, Vec<u8>)
+{
+    let data = packet.convert_versioned(version).unwrap();
+    let mut cursor = data.as_slice();
+    assert_eq!(mc_types::get_var_int(&mut cursor).unwrap(), 0);
+    let read = LoginStart::get_versioned(&mut cursor, version).unwrap();
+    assert!(cursor.is_empty());
+    (read, data)
+}
+
+#[test]
+fn login_start_without_uuid_stays_absent_before_1_20_2() {
+    let packet = LoginStart {
+        name: "Steve".to_string(),
+        has_player_uuid: false,
+        player_uuid: 0,
+    };
+    let (read, data) = round_trip(&packet, ProtocolVersion::V1_20_1);
+    assert_eq!(data, [0x00, 0x05, b'S', b't', b'e', b'v', b'e', 0x00]);
+    assert!(!read.has_player_uuid);
+    assert_eq!(read.convert_versioned(ProtocolVersion::V1_20_1).unwrap(),
+        data);
+}
+
+#[test]
+fn login_start_with_uuid_before_1_20_2() {
+    let packet = LoginStart {
+        name: "Steve".to_string(),
+        has_player_uuid: true,
+        player_uuid: UUID,
+    };
+    let (read, data) = round_trip(&packet, ProtocolVersion::V1_20_1);
+    assert_eq!(data.len(), 1 + 6 + 1 + 16);
+    assert!(read.has_player_uuid);
+    assert_eq!(read.player_uuid, UUID);
+}
+
+#[test]
+fn login_start_always_has_uuid_from_1_20_2() {
+    let packet = LoginStart {
+        name: "Steve".to_string(),
+        has_player_uuid: true,
+        player_uuid: UUID,
+    };
+    let (read, data) = round_trip(&packet, ProtocolVersion::V1_21);
+    assert_eq!(data.len(), 1 + 6 + 16);
+    assert!(read.has_player_uuid);
+    assert_eq!(read.player_uuid, UUID);
+}
@@ -0,0 +1,200 @@
+use std::borrow::Cow;
+
+use purple_cello_mc_protocol::mc_types::{
+    self, Packet, PacketArray, PacketError, ProtocolError};
+use purple_cello_mc_protocol::status::serverbound::Ping;
+
+const F32_CASES: &[(f32, [u8; 4])] = &[
+    (0.0, [0x00, 0x00, 0x00, 0x00]),
+    (-0.0, [0x80, 0x00, 0x00, 0x00]),
+    (1.0, [0x3F, 0x80, 0x00, 0x00]),
+    (20.0, [0x41, 0xA0, 0x00, 0x00]),
+    (-90.0, [0xC2, 0xB4, 0x00, 0x00]),
+    (0.1, [0x3D, 0xCC, 0xCC, 0xCD]),
+    (f32::INFINITY, [0x7F, 0x80, 0x00, 0x00]),
+];
+
+const F64_CASES: &[(f64, [u8; 8])] = &[
+    (0.0, [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    (0.5, [0x3F, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    (-1.5, [0xBF, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    (64.0, [0x40, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    (-2048.25, [0xC0, 0xA0, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00]),
+    (0.1, [0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A]),
+    (f64::NEG_INFINITY, [0xFF, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+];
+
+#[test]
+fn f32_matches_vanilla_bytes() {
+    for (value, bytes) in F32_CASES {
+        assert_eq!(mc_types::convert_f32(*value), bytes);
+        let mut data = &bytes[..];
+        let decoded = mc_types::get_f32(&mut data).unwrap();
+        assert_eq!(decoded.to_bits(), value.to_bits());
+        assert!(data.is_empty());
+    }
+}
+
+#[test]
+fn f64_matches_vanilla_bytes() {
+    for (value, bytes) in F64_CASES {
+        assert_eq!(mc_types::convert_f64(*value), bytes);
+        let mut data = &bytes[..];
+        let decoded = mc_types::get_f64(&mut data).unwrap();
+        assert_eq!(decoded.to_bits(), value.to_bits());
+        assert!(data.is_empty());
+    }
+}
+
+#[test]
+fn nan_round_trips_bit_exact() {
+    let value = f32::from_bits(0x7FC0_0001);
+    let data = mc_types::convert_f32(value);
+    let decoded = mc_types::get_f32(&mut data.as_slice()).unwrap();
+    assert_eq!(decoded.to_bits(), value.to_bits());
+
+    let value = f64::from_bits(0x7FF8_0000_0000_0001);
+    let data = mc_types::convert_f64(value);
+    let decoded = mc_types::get_f64(&mut data.as_slice()).unwrap();
+    assert_eq!(decoded.to_bits(), value.to_bits());
+}
+
+#[test]
+fn i32_uses_all_four_bytes() {
+    assert_eq!(mc_types::convert_i32(-1), [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(
+        mc_types::convert_i32(0x12345678),
+        [0x12, 0x34, 0x56, 0x78],
+    );
+    let mut data: &[u8] = &[0x80, 0x00, 0x00, 0x00];
+    assert_eq!(mc_types::get_i32(&mut data).unwrap(), i32::MIN);
+}
+
+#[test]
+fn truncated_float_is_an_error() {
+    let mut data: &[u8] = &[0x3F, 0x80, 0x00];
+    assert!(mc_types::get_f32(&mut data).is_err());
+    let mut data: &[u8] = &[0x3F, 0xE0, 0x00, 0x00];
+    assert!(mc_types::get_f64(&mut data).is_err());
+}
+
+#[test]
+fn cursor_advances_past_each_value() {
+    let mut data = mc_types::convert_var_int(300);
+    data.append(&mut mc_types::convert_string("hi"));
+    data.append(&mut mc_types::convert_u16(7));
+    let mut cursor = data.as_slice();
+    assert_eq!(mc_types::get_var_int(&mut cursor).unwrap(), 300);
+    assert_eq!(cursor.len(), data.len() - 2);
+    assert_eq!(mc_types::get_string(&mut cursor).unwrap(), "hi");
+    assert_eq!(mc_types::get_u16(&mut cursor).unwrap(), 7);
+    assert!(cursor.is_empty());
+}
+
+#[test]
+fn strings_and_byte_arrays_borrow_the_input() {
+    let data = mc_types::convert_string("borrowed");
+    let mut cursor = data.as_slice();
+    let value = mc_types::get_str(&mut cursor).unwrap();
+    assert!(matches!(value, Cow::Borrowed("borrowed")));
+
+    let data = mc_types::convert_byte_array(&[1, 2, 3]);
+    let bytes = mc_types::get_bytes(&mut data.as_slice()).unwrap();
+    assert_eq!(bytes, [1, 2, 3]);
+    assert!(std::ptr::eq(bytes.as_ptr(), data[1..].as_ptr()));
+}
+
+#[test]
+fn decoding_reports_bytes_consumed() {
+    let mut data = Ping { payload: 9 }.convert().unwrap();
+    data.remove(0);
+    data.extend_from_slice(&[0xAA, 0xBB]);
+    let (ping, consumed) = Ping::get_consumed(&data).unwrap();
+    assert_eq!(ping.payload, 9);
+    assert_eq!(consumed, 8);
+
+    let values = vec!["a".to_string(), "bc".to_string()];
+    let mut data = String::try_convert_array(&values).unwrap();
+    data.push(0xFF);
+    let mut cursor = data.as_slice();
+    assert_eq!(String::get_array(&mut cursor).unwrap(), values);
+    assert_eq!(cursor, [0xFF]);
+}
+
+fn ran_out_of<T: std::fmt::Debug>(
+    result: mc_types::Result<T>,
+) -> &'static str {
+    match result {
+        Err(ProtocolError::Decode(PacketError::RanOutOfBytes(field), _)) =>
+            field,
+        other => panic!("expected RanOutOfBytes, got {:?}", other),
+    }
+}
+
+#[test]
+fn truncated_primitives_run_out_of_bytes() {
+    let full = [0xFF; 16];
+    for length in 0..16 {
+        let data = &full[..length];
+        if length < 1 {
+            assert_eq!(ran_out_of(mc_types::get_bool(&mut &data[..])),
+                "Boolean");
+            assert_eq!(ran_out_of(mc_types::get_u8(&mut &data[..])),
+                "Unsigned Byte");
+            ran_out_of(mc_types::get_i8(&mut &data[..]));
+        }
+        if length < 2 {
+            assert_eq!(ran_out_of(mc_types::get_u16(&mut &data[..])),
+                "Unsigned Short");
+            ran_out_of(mc_types::get_i16(&mut &data[..]));
+        }
+        if length < 4 {
+            assert_eq!(ran_out_of(mc_types::get_u32(&mut &data[..])),
+                "Unsigned Int");
+            ran_out_of(mc_types::get_i32(&mut &data[..]));
+            ran_out_of(mc_types::get_f32(&mut &data[..]));
+        }
+        if length < 8 {
+            assert_eq!(ran_out_of(mc_types::get_u64(&mut &data[..])),
+                "Unsigned Long");
+            ran_out_of(mc_types::get_i64(&mut &data[..]));
+            ran_out_of(mc_types::get_f64(&mut &data[..]));
+        }
+        assert_eq!(ran_out_of(mc_types::get_uuid(&mut &data[..])), "UUID");
+        ran_out_of(mc_types::get_byte_array_fixed::<16>(&mut &data[..]));
+    }
+}
+
+#[test]
+fn truncated_var_ints_run_out_of_bytes() {
+    assert_eq!(ran_out_of(mc_types::get_var_int(&mut [].as_slice())),
+        "VarInt");
+    assert_eq!(ran_out_of(mc_types::get_var_int(&mut [0x80].as_slice())),
+        "VarInt");
+    assert_eq!(ran_out_of(mc_types::get_var_long(
+        &mut [0xFF, 0xFF, 0xFF].as_slice())), "VarLong");
+}
+
+#[test]
+fn truncated_length_prefixed_values_run_out_of_bytes() {
+    let mut string = mc_types::convert_string("hello");
+    string.pop();
+    assert_eq!(ran_out_of(mc_types::get_string(&mut string.as_slice())),
+        "String");
+    assert_eq!(ran_out_of(mc_types::get_str(&mut string.as_slice())),
+        "String");
+    assert_eq!(ran_out_of(mc_types::get_string(&mut [0x80].as_slice())),
+        "VarInt");
+
+    let mut bytes = mc_types::convert_byte_array(&[1, 2, 3]);
+    bytes.pop();
+    assert_eq!(ran_out_of(mc_types::get_byte_array(&mut bytes.as_slice())),
+        "Byte Array");
+    assert_eq!(ran_out_of(mc_types::get_bytes(&mut bytes.as_slice())),
+        "Byte Array");
+    assert_eq!(ran_out_of(mc_types::get_byte_array_limited(
+        &mut bytes.as_slice(), 16)), "Byte Array");
+    // A negative length cannot be satisfied either
+    let negative: &[u8] = &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F];
+    ran_out_of(mc_types::get_byte_array(&mut &negative[..]));
+}
@@ -0,0 +1,91 @@
+use purple_cello_mc_protocol::mc_types::{PacketError, ProtocolError};
+use purple_cello_mc_protocol::nbt::{self, Compound, Tag};
+
+fn nested_lists(depth: usize) -> Tag {
+    let mut tag = Tag::List(vec![]);
+    for _ in 1..depth {
+        tag = Tag::List(vec![tag]);
+    }
+    tag
+}
+
+fn is_invalid_nbt<T>(result: Result<T, ProtocolError>) -> bool {
+    matches!(result, Err(ProtocolError::Decode(PacketError::InvalidNbt(_), _)))
+}
+
+#[test]
+fn nesting_past_max_depth_is_rejected() {
+    let data = nbt::convert_network(&nested_lists(nbt::MAX_DEPTH)).unwrap();
+    assert!(nbt::get_network(&mut data.as_slice()).is_ok());
+    let data = nbt::convert_network(&nested_lists(nbt::MAX_DEPTH + 1))
+        .unwrap();
+    assert!(is_invalid_nbt(nbt::get_network(&mut data.as_slice())));
+}
+
+#[test]
+fn data_past_max_size_is_rejected() {
+    let tag = Tag::ByteArray(vec![0; nbt::MAX_SIZE]);
+    let data = nbt::convert_network(&tag).unwrap();
+    assert!(matches!(nbt::get_network(&mut data.as_slice()),
+        Err(ProtocolError::Decode(PacketError::LengthExceeded { .. }, _))));
+
+    let data = nbt::convert_network(&Tag::from("hello")).unwrap();
+    assert!(nbt::get_network_limited(&mut data.as_slice(), 8).is_ok());
+    assert!(nbt::get_network_limited(&mut data.as_slice(), 7).is_err());
+}
+
+#[test]
+fn modified_utf8_encodes_nul_and_surrogate_pairs() {
+    assert_eq!(nbt::encode_modified_utf8("a\0b"), [b'a', 0xC0, 0x80, b'b']);
+    // Characters outside the BMP are written as two 3 byte surrogates
+    let emoji = nbt::encode_modified_utf8("\u{1F600}");
+    assert_eq!(emoji, [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+    assert_eq!(nbt::decode_modified_utf8(&emoji).unwrap(), "\u{1F600}");
+    assert_eq!(nbt::decode_modified_utf8(&[0xC0, 0x80]).unwrap(), "\0");
+    // A lone surrogate is not a valid string
+    assert!(is_invalid_nbt(nbt::decode_modified_utf8(&[0xED, 0xA0, 0xBD])));
+}
+
+#[test]
+fn network_root_has_no_name() {
+    let mut compound = Compound::new();
+    compound.insert("a", Tag::Byte(1));
+    let tag = Tag::Compound(compound);
+    let data = nbt::convert_network(&tag).unwrap();
+    assert_eq!(data, [10, 1, 0, 1, b'a', 1, 0]);
+    assert_eq!(nbt::get_network(&mut data.as_slice()).unwrap(), Some(tag));
+
+    let named = nbt::convert_named("", &Tag::Byte(1)).unwrap();
+    assert_eq!(named, [1, 0, 0, 1]);
+    assert_eq!(nbt::get_network(&mut [0u8].as_slice()).unwrap(), None);
+}
+
+#[test]
+fn mixed_lists_are_wrapped_in_compounds() {
+    let list = Tag::List(vec![Tag::Int(1), Tag::from("a")]);
+    let data = nbt::convert_network(&list).unwrap();
+    assert_eq!(data, [
+        9, 10, 0, 0, 0, 2,
+        3, 0, 0, 0, 0, 0, 1, 0,
+        8, 0, 0, 0, 1, b'a', 0,
+    ]);
+
+    let wrap = |tag: Tag| {
+        let mut wrapper = Compound::new();
+        wrapper.insert("", tag);
+        Tag::Compound(wrapper)
+    };
+    let read = nbt::get_network(&mut data.as_slice()).unwrap().unwrap();
+    let wrapped = Tag::List(vec![wrap(Tag::Int(1)), wrap(Tag::from("a"))]);
+    assert_eq!(read, wrapped);
+    assert_eq!(nbt::convert_network(&wrapped).unwrap(), data);
+}
+
+#[test]
+fn strings_longer_than_u16_are_rejected() {
+    let long = "a".repeat(nbt::MAX_STRING_LENGTH + 1);
+    assert!(is_invalid_nbt(nbt::convert_network(&Tag::from(long.as_str()))));
+    assert!(is_invalid_nbt(nbt::convert_named(&long, &Tag::Byte(0))));
+    let fits = "a".repeat(nbt::MAX_STRING_LENGTH);
+    assert!(nbt::convert_network(&Tag::from(fits.as_str())).is_ok());
+}
@@ -0,0 +1,95 @@
+use purple_cello_mc_protocol::mc_types::{
+    self, Packet, PacketError, ProtocolError};
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+#[derive(Debug, PartialEq, Packet)]
+#[packet(id = 0x01)]
+pub struct Hello {
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq, Packet)]
+#[packet(id = 0x02, since = V1_20_2, ids(V1_20_2 = 0x03, V1_20_3 = 0x03))]
+pub struct Moved {
+    pub distance: i32,
+}
+
+#[derive(Debug, PartialEq, Packet)]
+#[packet(id = 0x01, until = V1_20_1)]
+pub struct Retired {}
+
+purple_cello_mc_protocol::packet_enum! {
+    #[derive(Debug, PartialEq)]
+    pub enum Known (Play, Clientbound) {
+        Hello(Hello),
+        Moved(Moved),
+    }
+}
+
+purple_cello_mc_protocol::packet_enum! {
+    #[derive(Debug, PartialEq)]
+    pub enum WithFallback (Play, Serverbound) {
+        Moved(Moved),
+        _ => Unknown,
+    }
+}
+
+fn hello() -> Hello {
+    Hello { name: "Steve".to_string() }
+}
+
+#[test]
+fn dispatches_on_packet_id() {
+    let data = hello().convert().unwrap();
+    let mut cursor = &data[1..];
+    assert_eq!(Known::get(data[0] as i32, &mut cursor).unwrap(),
+        Known::Hello(hello()));
+    assert_eq!(Known::from(hello()).convert().unwrap(), data);
+    assert_eq!(Known::Moved(Moved { distance: 3 }).packet_id(), 0x02);
+    assert_eq!(Known::STATE, mc_types::ConnectionState::Play);
+    assert_eq!(Known::DIRECTION, mc_types::Direction::Clientbound);
+}
+
+#[test]
+fn unknown_ids_are_errors_or_kept_raw() {
+    let result = Known::get(0x7F, &mut [1, 2].as_slice());
+    assert!(matches!(result,
+        Err(ProtocolError::Decode(PacketError::InvalidPacketId(0x7F), _))));
+    let unknown = WithFallback::get(0x7F, &mut [1, 2].as_slice()).unwrap();
+    assert_eq!(unknown, WithFallback::Unknown { id: 0x7F, data: vec![1, 2] });
+    assert_eq!(unknown.convert().unwrap(), [0x7F, 1, 2]);
+}
+
+#[test]
+fn ids_resolve_per_version() {
+    assert_eq!(Known::packet_ids_for(ProtocolVersion::V1_20_1), [0x01]);
+    assert_eq!(Known::packet_ids_for(ProtocolVersion::V1_21), [0x01, 0x02]);
+    assert_eq!(Moved::VERSIONED_IDS,
+        [None, Some(0x03), Some(0x03), Some(0x02), Some(0x02)]);
+    assert_eq!(Retired::packet_id_for(ProtocolVersion::V1_20_1), Some(0x01));
+    assert_eq!(Retired::packet_id_for(ProtocolVersion::V1_20_2), None);
+
+    let data = Moved { distance: 3 }
+        .convert_versioned(ProtocolVersion::V1_20_3).unwrap();
+    assert_eq!(data, [0x03, 0, 0, 0, 3]);
+    assert_eq!(Known::get_versioned(
+        0x03, &mut &data[1..], ProtocolVersion::V1_20_3).unwrap(),
+        Known::Moved(Moved { distance: 3 }));
+    assert!(Known::get_versioned(
+        0x03, &mut &data[1..], ProtocolVersion::V1_21).is_err());
+    assert!(Moved { distance: 3 }
+        .convert_versioned(ProtocolVersion::V1_20_1).is_err());
+}
+
+#[test]
+#[should_panic(expected = "duplicate packet id")]
+fn duplicate_ids_in_a_version_are_rejected() {
+    mc_types::assert_unique_packet_ids(&[Some(1), None, Some(1)]);
+}
+
+#[test]
+fn unavailable_packets_do_not_collide() {
+    mc_types::assert_unique_packet_ids(&[Some(1), None, None]);
+    assert_eq!(mc_types::versioned_ids(5, Some(ProtocolVersion::V1_20_5),
+        None, &[]), [None, None, None, Some(5), Some(5)]);
+}
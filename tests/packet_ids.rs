@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use purple_cello_mc_protocol::version::{ProtocolVersion, SUPPORTED_VERSIONS};
+use purple_cello_mc_protocol::{
+    configuration, handshake, login, mc_types, play, status};
+
+fn assert_unique(name: &str, ids: fn(ProtocolVersion) -> Vec<i32>) {
+    for version in SUPPORTED_VERSIONS {
+        let ids = ids(*version);
+        let mut seen = HashSet::new();
+        for id in &ids {
+            assert!(seen.insert(id),
+                "duplicate {} packet id {:#04x} in {}", name, id, version);
+        }
+    }
+}
+
+#[test]
+fn packet_ids_are_unique_in_every_version() {
+    assert_unique("handshake serverbound",
+        handshake::serverbound::HandshakeEnum::packet_ids_for);
+    assert_unique("status clientbound",
+        status::clientbound::StatusPackets::packet_ids_for);
+    assert_unique("status serverbound",
+        status::serverbound::StatusPackets::packet_ids_for);
+    assert_unique("login clientbound",
+        login::clientbound::Login::packet_ids_for);
+    assert_unique("login serverbound",
+        login::serverbound::Login::packet_ids_for);
+    assert_unique("configuration clientbound",
+        configuration::clientbound::Configuration::packet_ids_for);
+    assert_unique("configuration serverbound",
+        configuration::serverbound::Configuration::packet_ids_for);
+    assert_unique("play clientbound",
+        play::clientbound::Play::packet_ids_for);
+    assert_unique("play serverbound",
+        play::serverbound::Play::packet_ids_for);
+}
+
+#[test]
+fn versions_past_1_21_1_are_unsupported() {
+    assert_eq!(ProtocolVersion::from_protocol(767).unwrap(),
+        ProtocolVersion::LATEST);
+    assert!(ProtocolVersion::from_protocol(768).is_err());
+    assert!(ProtocolVersion::from_protocol(769).is_err());
+    assert_eq!(mc_types::VERSION_NAME, "1.21");
+}
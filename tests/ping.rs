@@ -0,0 +1,69 @@
+use purple_cello_mc_protocol::handshake::serverbound::{
+    HandshakeIntent, HandshakeStart};
+use purple_cello_mc_protocol::mc_types::{Packet, PacketError, ProtocolError};
+use purple_cello_mc_protocol::ping::{self, PingOptions};
+use purple_cello_mc_protocol::status::{
+    StatusResponder, clientbound, serverbound};
+
+mod common;
+
+use common::{Connection, connections};
+
+async fn read_status_handshake(conn: &mut Connection) {
+    match conn.read_handshake().await.unwrap() {
+        HandshakeStart::Handshake(handshake) => {
+            assert_eq!(handshake.server_address, "mc.example.com");
+            assert_eq!(handshake.server_port, 25565);
+            assert_eq!(handshake.intent().unwrap(), HandshakeIntent::Status);
+        },
+        HandshakeStart::LegacyPing(_) => panic!("expected a handshake"),
+    }
+}
+
+#[tokio::test]
+async fn ping_reads_status_and_latency() {
+    let (mut client, mut server) = connections();
+    let responder = StatusResponder::new()
+        .motd("Hello")
+        .players(3, 10);
+    let server = tokio::spawn(async move {
+        read_status_handshake(&mut server).await;
+        responder.respond(&mut server).await.unwrap();
+    });
+
+    let result = ping::ping_connection(
+        &mut client, "mc.example.com", 25565, &PingOptions::default())
+        .await
+        .unwrap();
+    server.await.unwrap();
+    assert_eq!(result.status.players.online, 3);
+    assert_eq!(result.status.players.max, 10);
+    assert_eq!(result.status.description.to_legacy(false), "Hello");
+    assert!(result.raw_json.contains("\"online\":3"));
+}
+
+#[tokio::test]
+async fn mismatched_pong_is_a_decode_error() {
+    let (mut client, mut server) = connections();
+    let server = tokio::spawn(async move {
+        read_status_handshake(&mut server).await;
+        serverbound::Status::read(&mut server).await.unwrap();
+        clientbound::Status {
+            response: serde_json::to_string(StatusResponder::new().data())
+                .unwrap(),
+        }.write(&mut server).await.unwrap();
+        let ping = serverbound::Ping::read(&mut server).await.unwrap();
+        clientbound::Ping { payload: ping.payload.wrapping_add(1) }
+            .write(&mut server).await.unwrap();
+    });
+
+    let result = ping::ping_connection(
+        &mut client, "mc.example.com", 25565, &PingOptions::default()).await;
+    server.await.unwrap();
+    let Err(error) = result else {
+        panic!("expected a pong mismatch");
+    };
+    assert!(matches!(error,
+        ProtocolError::Decode(PacketError::PongMismatch { .. }, _)));
+    assert_eq!(error.context().unwrap().packet_id, Some(1));
+}
@@ -0,0 +1,131 @@
+use purple_cello_mc_protocol::mc_types::{self, Packet};
+use purple_cello_mc_protocol::nbt::{Compound, Tag};
+use purple_cello_mc_protocol::play::clientbound::{
+    self, DeathLocation, Login, Play};
+use purple_cello_mc_protocol::play::serverbound;
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+mod common;
+
+fn login() -> Login {
+    let mut registry_codec = Compound::new();
+    registry_codec.insert("minecraft:dimension_type", Tag::Compound(
+        Compound::new()));
+    Login {
+        entity_id: 42,
+        is_hardcore: false,
+        dimension_names: vec!["minecraft:overworld".to_string()],
+        registry_codec,
+        max_players: 20,
+        view_distance: 10,
+        simulation_distance: 8,
+        reduced_debug_info: false,
+        enable_respawn_screen: true,
+        do_limited_crafting: false,
+        dimension_type: 0,
+        dimension_type_name: "minecraft:overworld".to_string(),
+        dimension_name: "minecraft:overworld".to_string(),
+        hashed_seed: -7,
+        game_mode: 1,
+        previous_game_mode: -1,
+        is_debug: false,
+        is_flat: true,
+        death_location: Some(DeathLocation {
+            dimension_name: "minecraft:the_nether".to_string(),
+            location: 0x1234,
+        }),
+        portal_cooldown: 0,
+        enforces_secure_chat: false,
+    }
+}
+
+fn read(data: &[u8], version: ProtocolVersion) -> Login {
+    let mut cursor = data;
+    let packet_id = mc_types::get_var_int(&mut cursor).unwrap();
+    match Play::get_versioned(packet_id, &mut cursor, version).unwrap() {
+        Play::Login(login) => {
+            assert!(cursor.is_empty());
+            login
+        },
+        _ => panic!("expected a login packet"),
+    }
+}
+
+#[test]
+fn login_uses_the_1_20_1_layout() {
+    let packet = login();
+    let data = packet.convert_versioned(ProtocolVersion::V1_20_1).unwrap();
+    // Game modes come straight after the hardcore flag
+    assert_eq!(&data[..7], &[0x28, 0, 0, 0, 42, 0, 1]);
+    let read = read(&data, ProtocolVersion::V1_20_1);
+    assert_eq!(read.registry_codec, packet.registry_codec);
+    assert_eq!(read.previous_game_mode, -1);
+    assert_eq!(read.max_players, 20);
+    assert_eq!(read.dimension_type_name, "minecraft:overworld");
+    assert_eq!(read.death_location.unwrap().location, 0x1234);
+}
+
+#[test]
+fn login_round_trips_in_newer_versions() {
+    let packet = login();
+    for version in [ProtocolVersion::V1_20_2, ProtocolVersion::V1_21] {
+        let data = packet.convert_versioned(version).unwrap();
+        assert_eq!(data[0] as i32, Login::packet_id_for(version).unwrap());
+        let read = read(&data, version);
+        assert!(read.registry_codec.is_empty());
+        assert_eq!(read.game_mode, 1);
+        assert_eq!(read.hashed_seed, -7);
+    }
+}
+
+#[tokio::test]
+async fn typed_packets_use_the_version_ids() {
+    let (mut client, mut server) = common::connections();
+    let version = ProtocolVersion::V1_20_2;
+    clientbound::KeepAlive { keep_alive_id: 99 }
+        .write_versioned(&mut server, version).await.unwrap();
+    match Play::read_versioned(&mut client, version).await.unwrap() {
+        Play::KeepAlive(keep_alive) =>
+            assert_eq!(keep_alive.keep_alive_id, 99),
+        _ => panic!("expected keep alive"),
+    }
+
+    serverbound::SetPlayerPosition {
+        x: 0.5,
+        feet_y: 64.0,
+        z: -3.25,
+        on_ground: true,
+    }.write_versioned(&mut client, version).await.unwrap();
+    match serverbound::Play::read_versioned(&mut server, version)
+        .await.unwrap()
+    {
+        serverbound::Play::SetPlayerPosition(position) => {
+            assert_eq!((position.x, position.feet_y, position.z),
+                (0.5, 64.0, -3.25));
+            assert!(position.on_ground);
+        },
+        _ => panic!("expected player position"),
+    }
+}
+
+#[test]
+fn unknown_packets_are_forwarded_untouched() {
+    let version = ProtocolVersion::V1_21;
+    let known = Play::packet_ids_for(version);
+    let id = (0..0x80).find(|id| !known.contains(id)).unwrap();
+    let mut data = mc_types::convert_var_int(id);
+    data.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+    let mut cursor = &data[1..];
+    let packet = Play::get_versioned(id, &mut cursor, version).unwrap();
+    assert!(cursor.is_empty());
+    match &packet {
+        Play::Unknown { id: unknown, data } => {
+            assert_eq!(*unknown, id);
+            assert_eq!(data, &[0xDE, 0xAD, 0xBE, 0xEF]);
+        },
+        _ => panic!("expected an unknown packet"),
+    }
+    assert_eq!(packet.packet_id_for(version), Some(id));
+    assert_eq!(packet.convert_versioned(version).unwrap(), data);
+}
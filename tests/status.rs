@@ -0,0 +1,107 @@
+use base64::Engine;
+
+use purple_cello_mc_protocol::favicon;
+use purple_cello_mc_protocol::status::StatusResponder;
+use purple_cello_mc_protocol::status::clientbound::{
+    StatusDescription, StatusResponseData};
+
+fn png(size: u32) -> Vec<u8> {
+    let mut out = vec![];
+    let mut encoder = png::Encoder::new(&mut out, size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&vec![0x80; (size * size * 4) as usize])
+        .unwrap();
+    writer.finish().unwrap();
+    out
+}
+
+fn round_trip(json: &str) -> StatusResponseData {
+    let data: StatusResponseData = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&data).unwrap(), json);
+    data
+}
+
+#[test]
+fn forge_1_12_modinfo_round_trips() {
+    let data = round_trip(concat!(
+        r#"{"description":{"text":"A Minecraft Server"},"#,
+        r#""players":{"max":20,"online":0},"#,
+        r#""version":{"name":"1.12.2","protocol":340},"#,
+        r#""modinfo":{"type":"FML","modList":["#,
+        r#"{"modid":"minecraft","version":"1.12.2"},"#,
+        r#"{"modid":"mcp","version":"9.42"},"#,
+        r#"{"modid":"FML","version":"8.0.99.99"},"#,
+        r#"{"modid":"forge","version":"14.23.5.2860"}]}}"#,
+    ));
+    assert!(matches!(data.description, StatusDescription::Chat(_)));
+    let modinfo = data.modinfo.unwrap();
+    assert_eq!(modinfo.mod_list[3].version.as_deref(),
+        Some("14.23.5.2860"));
+}
+
+#[test]
+fn forge_1_16_forge_data_round_trips() {
+    let data = round_trip(concat!(
+        r#"{"description":{"text":"A Minecraft Server"},"#,
+        r#""players":{"max":20,"online":1,"sample":["#,
+        r#"{"id":"069a79f4-44e9-4726-a5be-fca90e38aaf5","name":"Notch"}]},"#,
+        r#""version":{"name":"1.16.5","protocol":754},"#,
+        r#""forgeData":{"channels":["#,
+        r#"{"res":"forge:tier_sorting","version":"1.0","required":false},"#,
+        r#"{"res":"fml:handshake","version":"FML2","required":true}],"#,
+        r#""mods":[{"modId":"forge","modmarker":"ANY"},"#,
+        r#"{"modId":"minecraft","modmarker":"1.16.5"},"#,
+        r#"{"modId":"jei"}],"#,
+        r#""fmlNetworkVersion":2}}"#,
+    ));
+    let forge = data.forgeData.unwrap();
+    let channels = forge.channels.unwrap();
+    assert_eq!(channels[1].required, Some(true));
+    assert_eq!(forge.mods.unwrap()[2].modmarker, None);
+}
+
+#[test]
+fn unknown_nested_fields_are_kept_in_order() {
+    let data = round_trip(concat!(
+        r#"{"description":{"text":"A Fabric Server","color":"gold"},"#,
+        r#""players":{"max":100,"online":0},"#,
+        r#""version":{"name":"1.20.1","protocol":763},"#,
+        r#""enforcesSecureChat":false,"preventsChatReports":true,"#,
+        r#""forgeData":{"channels":["#,
+        r#"{"res":"example:sync","version":"3","required":true,"#,
+        r#""side":"both"}],"#,
+        r#""mods":[{"modId":"example","modmarker":"1.2","optional":true}],"#,
+        r#""fmlNetworkVersion":3,"d":"\u0000\u0001"},"#,
+        r#""isModded":true,"modpackData":{"projectID":1,"name":"Pack"}}"#,
+    ));
+    let forge = data.forgeData.unwrap();
+    assert_eq!(forge.channels.unwrap()[0].extra["side"], "both");
+    assert_eq!(forge.mods.unwrap()[0].extra["optional"], true);
+    assert_eq!(data.extra["isModded"], true);
+}
+
+#[test]
+fn plain_string_description_stays_a_string() {
+    let data = round_trip(concat!(
+        r#"{"description":"A Minecraft Server","#,
+        r#""players":{"max":20,"online":0},"#,
+        r#""version":{"name":"1.8.9","protocol":47}}"#,
+    ));
+    assert!(matches!(data.description, StatusDescription::String(_)));
+}
+
+#[test]
+fn responder_accepts_a_64x64_favicon() {
+    let icon = favicon::from_png(&png(64), false).unwrap();
+    let responder = StatusResponder::new().favicon(icon.clone()).unwrap();
+    assert_eq!(responder.data().favicon, Some(icon));
+}
+
+#[test]
+fn responder_rejects_invalid_favicons() {
+    assert!(StatusResponder::new().favicon("icon.png").is_err());
+    let small = format!("{}{}", favicon::FAVICON_PREFIX,
+        base64::engine::general_purpose::STANDARD.encode(png(16)));
+    assert!(StatusResponder::new().favicon(small).is_err());
+}
@@ -0,0 +1,264 @@
+use purple_cello_mc_protocol::mc_types::{
+    self, Packet, PacketError, ProtocolError};
+use purple_cello_mc_protocol::nbt::{Compound, Tag};
+use purple_cello_mc_protocol::play::clientbound::Disconnect;
+use purple_cello_mc_protocol::status::clientbound::StatusDescription;
+use purple_cello_mc_protocol::text::{
+    ClickAction, ClickEvent, HoverEvent, Style, TextComponent};
+use purple_cello_mc_protocol::version::ProtocolVersion;
+
+fn styled() -> TextComponent {
+    TextComponent::translatable("chat.type.text", vec![
+        TextComponent::text("Steve"),
+        TextComponent::text("hi").with_style(Style {
+            italic: Some(false),
+            ..Style::default()
+        }),
+    ])
+        .with_style(Style {
+            color: Some("gold".to_string()),
+            bold: Some(true),
+            click_event: Some(ClickEvent {
+                action: ClickAction::SuggestCommand,
+                value: "/msg Steve ".to_string(),
+            }),
+            hover_event: Some(HoverEvent::ShowText(
+                Box::new(TextComponent::text("Click to reply")))),
+            ..Style::default()
+        })
+        .append(TextComponent::keybind("key.jump"))
+}
+
+#[test]
+fn plain_text_becomes_an_nbt_string() {
+    assert_eq!(TextComponent::text("hello").to_nbt().unwrap(),
+        Tag::String("hello".to_string()));
+    assert_eq!(
+        TextComponent::from_nbt(&Tag::String("hello".to_string())).unwrap(),
+        TextComponent::text("hello"));
+}
+
+#[test]
+fn styled_component_round_trips_through_nbt() {
+    let component = styled();
+    let tag = component.to_nbt().unwrap();
+    let compound = tag.as_compound().unwrap();
+    assert_eq!(compound.get("bold"), Some(&Tag::Byte(1)));
+    assert_eq!(compound.get("translate").and_then(Tag::as_str),
+        Some("chat.type.text"));
+    assert_eq!(compound.get("with").and_then(Tag::as_list).unwrap().len(),
+        2);
+    assert_eq!(TextComponent::from_nbt(&tag).unwrap(), component);
+}
+
+#[test]
+fn mixed_nbt_lists_become_json_arrays() {
+    let mut wrapped = Compound::new();
+    wrapped.insert("", Tag::Int(3));
+    let mut compound = Compound::new();
+    compound.insert("translate", Tag::from("commands.drop.success"));
+    compound.insert("with", Tag::List(vec![
+        Tag::Compound(wrapped),
+        Tag::from("Stone"),
+    ]));
+    let component = TextComponent::from_nbt(&Tag::Compound(compound))
+        .unwrap();
+    assert_eq!(component, TextComponent::translatable(
+        "commands.drop.success", vec![
+            TextComponent::text("3"),
+            TextComponent::text("Stone"),
+        ]));
+}
+
+#[test]
+fn component_codec_follows_version() {
+    let component = styled();
+    let nbt = mc_types::convert_component(
+        &component, ProtocolVersion::V1_20_3).unwrap();
+    assert_eq!(nbt, mc_types::convert_nbt(&component.to_nbt().unwrap())
+        .unwrap());
+    let json = mc_types::convert_component(
+        &component, ProtocolVersion::V1_20_2).unwrap();
+    assert_eq!(json, mc_types::convert_json_component(&component).unwrap());
+
+    assert_eq!(mc_types::get_component(
+        &mut nbt.as_slice(), ProtocolVersion::V1_20_3).unwrap(), component);
+    assert_eq!(mc_types::get_component(
+        &mut json.as_slice(), ProtocolVersion::V1_20_2).unwrap(), component);
+}
+
+#[test]
+fn packet_components_follow_version() {
+    let packet = Disconnect { reason: styled() };
+    for version in [ProtocolVersion::V1_20_2, ProtocolVersion::V1_21] {
+        let data = packet.convert_versioned(version).unwrap();
+        let mut cursor = data.as_slice();
+        mc_types::get_var_int(&mut cursor).unwrap();
+        let read = Disconnect::get_versioned(&mut cursor, version).unwrap();
+        assert_eq!(read.reason, packet.reason);
+    }
+}
+
+#[test]
+fn oversized_nbt_components_are_encoding_errors() {
+    let packet = Disconnect {
+        reason: TextComponent::text("a".repeat(70000)),
+    };
+    assert!(matches!(packet.convert(),
+        Err(ProtocolError::Decode(PacketError::InvalidNbt(_), _))));
+    assert!(packet.convert_versioned(ProtocolVersion::V1_20_2).is_ok());
+}
+
+#[test]
+fn string_shorthand_is_plain_text() {
+    let component: TextComponent = serde_json::from_str("\"hi\"").unwrap();
+    assert_eq!(component, TextComponent::text("hi"));
+    assert_eq!(serde_json::to_string(&component).unwrap(), "\"hi\"");
+}
+
+#[test]
+fn array_shorthand_appends_siblings() {
+    let component: TextComponent =
+        serde_json::from_str(r#"["a",{"text":"b","bold":true},"c"]"#)
+            .unwrap();
+    assert_eq!(component, TextComponent::text("a")
+        .append(TextComponent::text("b").with_style(Style {
+            bold: Some(true),
+            ..Style::default()
+        }))
+        .append(TextComponent::text("c")));
+    assert_eq!(serde_json::to_string(&component).unwrap(),
+        r#"{"text":"a","extra":[{"text":"b","bold":true},"c"]}"#);
+}
+
+#[test]
+fn click_and_hover_events_round_trip() {
+    let json = concat!(
+        r#"{"text":"x","#,
+        r#""clickEvent":{"action":"open_url","value":"https://a.b"},"#,
+        r#""hoverEvent":{"action":"show_text","contents":"tip"}}"#,
+    );
+    let component: TextComponent = serde_json::from_str(json).unwrap();
+    assert_eq!(component.style.click_event.as_ref().unwrap().action,
+        ClickAction::OpenUrl);
+    assert_eq!(component.style.hover_event,
+        Some(HoverEvent::ShowText(Box::new(TextComponent::text("tip")))));
+    assert_eq!(serde_json::to_string(&component).unwrap(), json);
+}
+
+#[test]
+fn legacy_hover_value_is_accepted() {
+    let component: TextComponent = serde_json::from_str(concat!(
+        r#"{"text":"x","#,
+        r#""hoverEvent":{"action":"show_text","value":"old"}}"#,
+    )).unwrap();
+    assert_eq!(component.style.hover_event,
+        Some(HoverEvent::ShowText(Box::new(TextComponent::text("old")))));
+}
+
+#[test]
+fn show_item_and_entity_hovers_round_trip() {
+    let item: HoverEvent = serde_json::from_str(
+        r#"{"action":"show_item","contents":"minecraft:stone"}"#).unwrap();
+    let HoverEvent::ShowItem(item) = item else {
+        panic!("expected show_item");
+    };
+    assert_eq!(item.id, "minecraft:stone");
+
+    let json = concat!(
+        r#"{"action":"show_entity","contents":{"type":"minecraft:pig","#,
+        r#""id":[1,2,3,4],"name":"Pig"}}"#,
+    );
+    let entity: HoverEvent = serde_json::from_str(json).unwrap();
+    assert!(matches!(entity, HoverEvent::ShowEntity(_)));
+    assert_eq!(serde_json::to_string(&entity).unwrap(), json);
+}
+
+#[test]
+fn pre_1_20_5_show_item_round_trips() {
+    let json: serde_json::Value = serde_json::from_str(concat!(
+        r#"{"action":"show_item","contents":{"id":"minecraft:diamond","#,
+        r#""Count":2,"tag":"{display:{Name:'\"Gem\"'}}"}}"#,
+    )).unwrap();
+    let item: HoverEvent = serde_json::from_value(json.clone()).unwrap();
+    let HoverEvent::ShowItem(ref stack) = item else {
+        panic!("expected show_item");
+    };
+    assert_eq!(stack.tag.as_deref(), Some(r#"{display:{Name:'"Gem"'}}"#));
+    assert_eq!(stack.extra["Count"], 2);
+    assert_eq!(serde_json::to_value(&item).unwrap(), json);
+}
+
+fn colored(text: &str, color: &str) -> TextComponent {
+    TextComponent::text(text).with_style(Style {
+        color: Some(color.to_string()),
+        ..Style::default()
+    })
+}
+
+#[test]
+fn legacy_codes_become_styled_siblings() {
+    let component = TextComponent::from_legacy("\u{a7}6Hello \u{a7}lWorld");
+    assert_eq!(component, TextComponent::default()
+        .append(colored("Hello ", "gold"))
+        .append(TextComponent::text("World").with_style(Style {
+            color: Some("gold".to_string()),
+            bold: Some(true),
+            ..Style::default()
+        })));
+    assert_eq!(component.to_legacy(), "\u{a7}6Hello \u{a7}lWorld");
+}
+
+#[test]
+fn legacy_reset_clears_style() {
+    let component = TextComponent::from_legacy(
+        "\u{a7}c\u{a7}lA\u{a7}rB");
+    assert_eq!(component.extra[1], TextComponent::text("B"));
+    assert_eq!(component.to_legacy(), "\u{a7}c\u{a7}lA\u{a7}rB");
+}
+
+#[test]
+fn legacy_hex_colors_are_parsed() {
+    let component = TextComponent::from_legacy(
+        "\u{a7}x\u{a7}F\u{a7}f\u{a7}0\u{a7}0\u{a7}0\u{a7}0Red");
+    assert_eq!(component, colored("Red", "#ff0000"));
+    // Unknown codes and a trailing marker are kept as text
+    assert_eq!(TextComponent::from_legacy("\u{a7}zA\u{a7}"),
+        TextComponent::text("\u{a7}zA\u{a7}"));
+}
+
+#[test]
+fn hex_colors_round_to_named_colors_before_1_16() {
+    let component = colored("Red", "#ff0000");
+    assert_eq!(component.to_legacy_with('\u{a7}', true),
+        "\u{a7}x\u{a7}f\u{a7}f\u{a7}0\u{a7}0\u{a7}0\u{a7}0Red");
+    assert_eq!(component.to_legacy_with('\u{a7}', false), "\u{a7}4Red");
+    assert_eq!(colored("Hi", "#fffffe").to_legacy_with('&', false), "&fHi");
+}
+
+#[test]
+fn legacy_output_resets_removed_styles() {
+    let parent = TextComponent::text("A").with_style(Style {
+        color: Some("red".to_string()),
+        bold: Some(true),
+        ..Style::default()
+    });
+    let unbold = parent.clone().append(TextComponent::text("B")
+        .with_style(Style {
+            bold: Some(false),
+            ..Style::default()
+        }));
+    assert_eq!(unbold.to_legacy(), "\u{a7}c\u{a7}lA\u{a7}cB");
+    let reset = parent.append(colored("B", "reset"));
+    assert_eq!(reset.to_legacy(), "\u{a7}c\u{a7}lA\u{a7}r\u{a7}lB");
+}
+
+#[test]
+fn status_description_to_legacy() {
+    let string = StatusDescription::String("\u{a7}aHi".to_string());
+    assert_eq!(string.to_legacy(false), "\u{a7}aHi");
+    let chat = StatusDescription::Chat(colored("Hi", "#00ff00"));
+    assert_eq!(chat.to_legacy(false), "\u{a7}2Hi");
+    assert_eq!(chat.to_legacy(true),
+        "\u{a7}x\u{a7}0\u{a7}0\u{a7}f\u{a7}f\u{a7}0\u{a7}0Hi");
+}
@@ -0,0 +1,142 @@
+use purple_cello_mc_protocol::login::clientbound::LoginSuccessProperty;
+use purple_cello_mc_protocol::mc_types::{PacketError, ProtocolError};
+use purple_cello_mc_protocol::velocity::{self, VelocityForwarding,
+    VelocityPlayerKey};
+use purple_cello_mc_protocol::version::SUPPORTED_VERSIONS;
+
+const SECRET: &[u8] = b"forwarding-secret";
+
+fn key(signer: Option<u128>) -> VelocityPlayerKey {
+    VelocityPlayerKey {
+        expires_at: 1_700_000_000_000,
+        public_key: vec![1, 2, 3, 4],
+        key_signature: vec![5, 6, 7],
+        signer,
+    }
+}
+
+fn forwarding(version: i32, key: Option<VelocityPlayerKey>)
+    -> VelocityForwarding
+{
+    VelocityForwarding {
+        version,
+        client_address: "203.0.113.7".parse().unwrap(),
+        uuid: 0x069a79f444e94726a5befca90e38aaf5,
+        username: "Notch".to_string(),
+        properties: vec![LoginSuccessProperty {
+            name: "textures".to_string(),
+            value: "e30=".to_string(),
+            signature: Some("c2ln".to_string()),
+        }],
+        key,
+    }
+}
+
+fn round_trip(forwarding: &VelocityForwarding) {
+    let data = forwarding.encode(SECRET).unwrap();
+    assert_eq!(&VelocityForwarding::verify(&data, SECRET).unwrap(),
+        forwarding);
+}
+
+#[test]
+fn default_version_round_trips() {
+    round_trip(&forwarding(velocity::MODERN_DEFAULT, None));
+}
+
+#[test]
+fn with_key_round_trips() {
+    round_trip(&forwarding(velocity::MODERN_WITH_KEY, Some(key(None))));
+}
+
+#[test]
+fn with_key_v2_round_trips() {
+    round_trip(&forwarding(velocity::MODERN_WITH_KEY_V2,
+        Some(key(Some(0x1234)))));
+    round_trip(&forwarding(velocity::MODERN_WITH_KEY_V2, Some(key(None))));
+}
+
+#[test]
+fn lazy_session_round_trips() {
+    round_trip(&forwarding(velocity::MODERN_LAZY_SESSION, None));
+}
+
+#[test]
+fn key_versions_require_a_key() {
+    for version in [velocity::MODERN_WITH_KEY, velocity::MODERN_WITH_KEY_V2] {
+        assert!(matches!(forwarding(version, None).encode(SECRET),
+            Err(ProtocolError::Decode(PacketError::InvalidForwarding(_), _))));
+    }
+    assert!(forwarding(velocity::MODERN_WITH_KEY, Some(key(Some(1))))
+        .encode(SECRET).is_err());
+}
+
+#[test]
+fn wrong_secret_is_rejected() {
+    let data = forwarding(velocity::MODERN_DEFAULT, None)
+        .encode(SECRET).unwrap();
+    assert!(matches!(VelocityForwarding::verify(&data, b"other-secret"),
+        Err(ProtocolError::Authentication(_))));
+}
+
+#[test]
+fn truncated_payload_is_rejected() {
+    let data = forwarding(velocity::MODERN_WITH_KEY, Some(key(None)))
+        .encode(SECRET).unwrap();
+    assert!(VelocityForwarding::verify(&data[..16], SECRET).is_err());
+    assert!(matches!(
+        VelocityForwarding::verify(&data[..data.len() - 2], SECRET),
+        Err(ProtocolError::Authentication(_))));
+    // A correctly signed but truncated payload fails while decoding
+    let resigned = resign(&data[32..data.len() - 2]);
+    assert!(matches!(VelocityForwarding::verify(&resigned, SECRET),
+        Err(ProtocolError::Decode(PacketError::RanOutOfBytes(_), _))));
+}
+
+#[test]
+fn trailing_data_is_rejected() {
+    let data = forwarding(velocity::MODERN_DEFAULT, None)
+        .encode(SECRET).unwrap();
+    let mut payload = data[32..].to_vec();
+    payload.push(0);
+    assert!(matches!(VelocityForwarding::verify(&resign(&payload), SECRET),
+        Err(ProtocolError::Decode(PacketError::InvalidForwarding(_), _))));
+}
+
+#[test]
+fn supported_clients_never_forward_keys() {
+    let negotiate = VelocityForwarding::negotiate_version;
+    for version in SUPPORTED_VERSIONS {
+        let protocol = version.protocol();
+        for requested in 1..=3 {
+            assert_eq!(negotiate(requested, protocol, Some(&key(Some(1)))),
+                velocity::MODERN_DEFAULT);
+        }
+        assert_eq!(negotiate(4, protocol, Some(&key(None))),
+            velocity::MODERN_LAZY_SESSION);
+        assert_eq!(negotiate(5, protocol, None),
+            velocity::MODERN_LAZY_SESSION);
+    }
+}
+
+#[test]
+fn negotiates_version_for_key_before_1_19_3() {
+    let negotiate = |requested, key: Option<VelocityPlayerKey>| {
+        VelocityForwarding::negotiate_version(requested, 760, key.as_ref())
+    };
+    assert_eq!(negotiate(1, None), velocity::MODERN_DEFAULT);
+    assert_eq!(negotiate(4, None), velocity::MODERN_DEFAULT);
+    assert_eq!(negotiate(2, Some(key(None))), velocity::MODERN_WITH_KEY);
+    assert_eq!(negotiate(3, Some(key(None))), velocity::MODERN_WITH_KEY);
+    assert_eq!(negotiate(2, Some(key(Some(1)))), velocity::MODERN_DEFAULT);
+    assert_eq!(negotiate(4, Some(key(Some(1)))),
+        velocity::MODERN_WITH_KEY_V2);
+}
+
+fn resign(payload: &[u8]) -> Vec<u8> {
+    use hmac::{Hmac, Mac};
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(SECRET).unwrap();
+    mac.update(payload);
+    let mut out = mac.finalize().into_bytes().to_vec();
+    out.extend_from_slice(payload);
+    out
+}